@@ -0,0 +1,94 @@
+use inflector::Inflector;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, FnArg, ImplItem, ItemImpl, ReturnType, Type};
+
+/// Implement `TestObject` for the type of an `impl` block of a design problem.
+///
+/// Every method taking `self` is dispatched by its LeetCode (camelCase) name as well as its
+/// Rust name, parameters are read with `FromLeetcodeJson` and results written with
+/// `ToLeetcodeJson`. The class name (the constructor step) returns `None`, any other method
+/// panics.
+///
+/// ```ignore
+/// #[test_object]
+/// impl MinStack {
+///     fn new() -> Self { .. }
+///     fn get_min(&self) -> i32 { .. }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test_object(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item_impl = parse_macro_input!(item as ItemImpl);
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    // the first step of an example constructs the object, which is already done
+    let class_name = match &**self_ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+    .map(|name| quote!(#name => None,));
+
+    let arms = item_impl.items.iter().filter_map(|item| {
+        let method = match item {
+            ImplItem::Fn(method) if method.sig.receiver().is_some() => method,
+            _ => return None,
+        };
+        let ident = &method.sig.ident;
+        let rust_name = ident.to_string();
+        let leetcode_name = rust_name.to_camel_case();
+        let args = method
+            .sig
+            .inputs
+            .iter()
+            .filter(|arg| matches!(arg, FnArg::Typed(_)))
+            .enumerate()
+            .map(|(i, _)| {
+                quote!(::leetcode_tool::prelude::FromLeetcodeJson::from_leetcode_json(&params[#i]))
+            });
+        let call = quote!(self.#ident(#(#args),*));
+        let returns_unit = match &method.sig.output {
+            ReturnType::Default => true,
+            ReturnType::Type(_, ty) => matches!(&**ty, Type::Tuple(t) if t.elems.is_empty()),
+        };
+        let body = if returns_unit {
+            quote! {
+                #call;
+                None
+            }
+        } else {
+            quote! {
+                let res = #call;
+                Some(::leetcode_tool::prelude::ToLeetcodeJson::to_leetcode_json(&res))
+            }
+        };
+        let pattern = if leetcode_name == rust_name {
+            quote!(#rust_name)
+        } else {
+            quote!(#leetcode_name | #rust_name)
+        };
+        Some(quote! {
+            #pattern => { #body }
+        })
+    });
+
+    quote! {
+        #item_impl
+
+        impl #impl_generics ::leetcode_tool::prelude::TestObject for #self_ty #where_clause {
+            fn call(
+                &mut self,
+                method: &str,
+                params: &[::leetcode_tool::prelude::Value],
+            ) -> Option<::leetcode_tool::prelude::Value> {
+                match method {
+                    #class_name
+                    #(#arms)*
+                    _ => panic!("unknown method {}", method),
+                }
+            }
+        }
+    }
+    .into()
+}
@@ -0,0 +1,509 @@
+//! Input bounds from the "提示"/"Constraints" section of the problem statement, e.g.
+//! `1 <= nums.length <= 10^4`, `costs[i].length == 3` or "nums 中的所有整数互不相同".
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::guest::html_to_text;
+
+/// Something a constraint bounds: `nums.length` is `Length("nums", 0)`, `nums[i].length` is
+/// `Length("nums", 1)`, `nums[i]` is `Value("nums", 1)` and `n` is `Value("n", 0)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term {
+    Length(String, usize),
+    Value(String, usize),
+}
+
+impl Term {
+    pub fn name(&self) -> &str {
+        match self {
+            Term::Length(name, _) | Term::Value(name, _) => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bound {
+    Int(i64),
+    /// Another term plus an offset, `k < nums.length` is `Ref(Length("nums", 0), -1)`.
+    Ref(Term, i64),
+}
+
+/// Inclusive bounds, every one of them holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Range {
+    pub min: Vec<Bound>,
+    pub max: Vec<Bound>,
+}
+
+impl Range {
+    /// The tightest numeric lower bound.
+    pub fn int_min(&self) -> Option<i64> {
+        Self::ints(&self.min).max()
+    }
+
+    /// The tightest numeric upper bound.
+    pub fn int_max(&self) -> Option<i64> {
+        Self::ints(&self.max).min()
+    }
+
+    /// The only value allowed, `costs[i].length == 3`.
+    pub fn fixed(&self) -> Option<i64> {
+        self.int_min().filter(|min| Some(*min) == self.int_max())
+    }
+
+    fn ints(bounds: &[Bound]) -> impl Iterator<Item = i64> + '_ {
+        bounds.iter().filter_map(|b| match b {
+            Bound::Int(v) => Some(*v),
+            Bound::Ref(..) => None,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.min.is_empty() && self.max.is_empty()
+    }
+
+    fn add_min(&mut self, bound: Bound) {
+        if !self.min.contains(&bound) {
+            self.min.push(bound);
+        }
+    }
+
+    fn add_max(&mut self, bound: Bound) {
+        if !self.max.contains(&bound) {
+            self.max.push(bound);
+        }
+    }
+}
+
+/// What the statement says about one parameter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// Length bounds per nesting level, `nums.length` then `nums[i].length`.
+    pub lengths: Vec<Range>,
+    /// Bounds of the value, or of the innermost elements of a list.
+    pub values: Range,
+    pub unique: bool,
+    /// Sorted in non-decreasing order.
+    pub sorted: bool,
+    /// The characters strings consist of.
+    pub alphabet: Option<String>,
+}
+
+impl Constraints {
+    /// Length bounds at nesting level `depth`.
+    pub fn length(&self, depth: usize) -> Option<&Range> {
+        self.lengths.get(depth).filter(|r| !r.is_empty())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lengths.iter().all(Range::is_empty)
+            && self.values.is_empty()
+            && !self.unique
+            && !self.sorted
+            && self.alphabet.is_none()
+    }
+
+    fn range_mut(&mut self, term: &Term) -> &mut Range {
+        match term {
+            Term::Length(_, depth) => {
+                if self.lengths.len() <= *depth {
+                    self.lengths.resize(depth + 1, Range::default());
+                }
+                &mut self.lengths[*depth]
+            }
+            Term::Value(..) => &mut self.values,
+        }
+    }
+}
+
+/// The items of the constraints section as HTML, empty when there is none.
+fn constraint_items(html: &str) -> Vec<&str> {
+    lazy_static! {
+        static ref HEAD_RE: Regex = Regex::new(r"<(strong|b)[^>]*>\s*(提示|Constraints)").unwrap();
+        static ref END_RE: Regex = Regex::new(r"<(strong|b)[^>]*>\s*(进阶|Follow)|</ul>").unwrap();
+        static ref ITEM_RE: Regex = Regex::new(r"(?s)<li>(.*?)</li>").unwrap();
+    }
+    let start = match HEAD_RE.find(html) {
+        Some(m) => m.end(),
+        None => return vec![],
+    };
+    let end = END_RE
+        .find_at(html, start)
+        .map_or(html.len(), |m| m.start());
+    ITEM_RE
+        .captures_iter(&html[start..end])
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect()
+}
+
+/// `10<sup>4</sup>` is `10^4` once the tags are gone.
+fn item_text(html: &str) -> String {
+    lazy_static! {
+        static ref SUP_RE: Regex = Regex::new(r"<sup>\s*(.*?)\s*</sup>").unwrap();
+    }
+    html_to_text(&SUP_RE.replace_all(html, "^$1"))
+        .replace('≤', "<=")
+        .replace('≥', ">=")
+        .replace('−', "-")
+}
+
+/// Evaluate `-2^31`, `2^31 - 1`, `5 * 10^4`, ...
+fn eval_number(s: &str) -> Option<i64> {
+    fn expr(tokens: &[String], pos: &mut usize) -> Option<f64> {
+        let mut value = term(tokens, pos)?;
+        while let Some(op @ ("+" | "-")) = tokens.get(*pos).map(String::as_str) {
+            *pos += 1;
+            let rhs = term(tokens, pos)?;
+            value = if op == "+" { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+    fn term(tokens: &[String], pos: &mut usize) -> Option<f64> {
+        let mut value = factor(tokens, pos)?;
+        while let Some("*" | "x" | "×") = tokens.get(*pos).map(String::as_str) {
+            *pos += 1;
+            value *= factor(tokens, pos)?;
+        }
+        Some(value)
+    }
+    fn factor(tokens: &[String], pos: &mut usize) -> Option<f64> {
+        if tokens.get(*pos)? == "-" {
+            *pos += 1;
+            return factor(tokens, pos).map(|v| -v);
+        }
+        let base = tokens.get(*pos)?.parse::<f64>().ok()?;
+        *pos += 1;
+        if tokens.get(*pos).map(String::as_str) == Some("^") {
+            *pos += 1;
+            return Some(base.powf(factor(tokens, pos)?));
+        }
+        Some(base)
+    }
+
+    lazy_static! {
+        static ref TOKEN_RE: Regex = Regex::new(r"\d+(?:\.\d+)?|[-+*^x×]|\S").unwrap();
+    }
+    let tokens = TOKEN_RE
+        .find_iter(s)
+        .map(|m| m.as_str().to_owned())
+        .collect::<Vec<_>>();
+    let mut pos = 0;
+    let value = expr(&tokens, &mut pos)?;
+    (pos == tokens.len()).then_some(value.round() as i64)
+}
+
+/// `nums.length`, `grid[i][j]` or `n`, without resolving the name.
+fn parse_term(s: &str) -> Option<Term> {
+    lazy_static! {
+        static ref TERM_RE: Regex =
+            Regex::new(r"^([A-Za-z_]\w*)((?:\[[^\]]*\])*)(\.length|\.size\(\)|\.len\(\))?$")
+                .unwrap();
+    }
+    let caps = TERM_RE.captures(s.trim())?;
+    let name = caps[1].to_owned();
+    let depth = caps[2].matches('[').count();
+    Some(match caps.get(3) {
+        Some(_) => Term::Length(name, depth),
+        None => Term::Value(name, depth),
+    })
+}
+
+/// One side of a comparison.
+#[derive(Debug)]
+enum Side {
+    Number(i64),
+    Terms(Vec<(Term, i64)>),
+}
+
+struct Parser<'a> {
+    params: &'a [&'a str],
+    aliases: HashMap<String, Term>,
+}
+
+impl Parser<'_> {
+    fn resolve(&self, term: Term) -> Option<Term> {
+        match term {
+            Term::Value(name, 0) if !self.params.contains(&name.as_str()) => {
+                self.aliases.get(&name).cloned()
+            }
+            t if self.params.contains(&t.name()) => Some(t),
+            _ => None,
+        }
+    }
+
+    fn parse_side(&self, s: &str) -> Option<Side> {
+        lazy_static! {
+            static ref OFFSET_RE: Regex = Regex::new(r"^(.+?)\s*([-+])\s*(\d+)$").unwrap();
+        }
+        if let Some(v) = eval_number(s) {
+            return Some(Side::Number(v));
+        }
+        let mut terms = vec![];
+        for part in s.split(',') {
+            let (term, offset) = match OFFSET_RE.captures(part.trim()) {
+                Some(caps) => {
+                    let offset = caps[3].parse::<i64>().ok()?;
+                    let offset = if &caps[2] == "-" { -offset } else { offset };
+                    (parse_term(&caps[1])?, offset)
+                }
+                None => (parse_term(part)?, 0),
+            };
+            terms.push((self.resolve(term)?, offset));
+        }
+        Some(Side::Terms(terms))
+    }
+}
+
+/// Split `1 <= m, n <= 200` into sides and operators.
+fn split_comparisons(s: &str) -> (Vec<&str>, Vec<&str>) {
+    lazy_static! {
+        static ref OP_RE: Regex = Regex::new(r"<=|>=|==|!=|<|>").unwrap();
+    }
+    let ops = OP_RE.find_iter(s).map(|m| m.as_str()).collect();
+    let sides = OP_RE.split(s).map(str::trim).collect();
+    (sides, ops)
+}
+
+/// The constraints of the statement (HTML) as text lines: the comparisons in the `<code>` spans
+/// of each item, and the whole item when it says more than them.
+pub fn constraint_lines(html: &str) -> Vec<String> {
+    lazy_static! {
+        static ref CODE_RE: Regex = Regex::new(r"(?s)<code>(.*?)</code>").unwrap();
+    }
+    let mut lines = vec![];
+    for item in constraint_items(html) {
+        let codes = CODE_RE
+            .captures_iter(item)
+            .map(|c| item_text(&c[1]))
+            .filter(|s| !split_comparisons(s).1.is_empty())
+            .collect::<Vec<_>>();
+        let rest = CODE_RE.replace_all(item, |c: &regex::Captures| {
+            if split_comparisons(&item_text(&c[1])).1.is_empty() {
+                c[0].to_owned()
+            } else {
+                String::new()
+            }
+        });
+        lines.extend(codes);
+        if item_text(&rest).chars().any(char::is_alphanumeric) {
+            lines.push(item_text(item));
+        }
+    }
+    lines
+}
+
+fn alphabet(text: &str) -> Option<String> {
+    lazy_static! {
+        static ref QUOTED_RE: Regex = Regex::new(r"'(.)'").unwrap();
+    }
+    let lower = text.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+    let letters = has(&["english letters", "英文字母"]);
+    let mut alphabet = String::new();
+    if has(&["lowercase", "小写"]) || (letters && !has(&["uppercase", "大写"])) {
+        alphabet.extend('a'..='z');
+    }
+    if has(&["uppercase", "大写"]) || (letters && !has(&["lowercase", "小写"])) {
+        alphabet.extend('A'..='Z');
+    }
+    if has(&["digit", "数字"]) {
+        alphabet.extend('0'..='9');
+    }
+    for caps in QUOTED_RE.captures_iter(text) {
+        let c = caps[1].chars().next().unwrap();
+        if !alphabet.contains(c) {
+            alphabet.push(c);
+        }
+    }
+    (!alphabet.is_empty()).then_some(alphabet)
+}
+
+/// Constraints of the parameters named `params` from the problem statement (HTML).
+pub fn parse_constraints(html: &str, params: &[&str]) -> HashMap<String, Constraints> {
+    parse_constraint_lines(&constraint_lines(html), params)
+}
+
+/// Constraints of the parameters named `params` from lines like `1 <= nums.length <= 10^4`.
+pub fn parse_constraint_lines<S: AsRef<str>>(
+    lines: &[S],
+    params: &[&str],
+) -> HashMap<String, Constraints> {
+    lazy_static! {
+        static ref UNIQUE_RE: Regex =
+            Regex::new(r"(?i)unique|distinct|互不相同|各不相同|不重复").unwrap();
+        static ref SORTED_RE: Regex =
+            Regex::new(r"(?i)sorted|ascending|non-decreasing|升序|非递减").unwrap();
+        // `sorted` only records non-decreasing order
+        static ref DESCENDING_RE: Regex =
+            Regex::new(r"(?i)non-increasing|descending|(^|[^-])\bdecreasing|降序|非递增").unwrap();
+        static ref WORD_RE: Regex = Regex::new(r"[A-Za-z_]\w*").unwrap();
+    }
+    let mut parser = Parser {
+        params,
+        aliases: HashMap::new(),
+    };
+    // `n == nums.length` names a length used by the other items
+    for line in lines {
+        let (sides, ops) = split_comparisons(line.as_ref());
+        if let ([lhs, rhs], ["=="]) = (&sides[..], &ops[..]) {
+            for (alias, target) in [(lhs, rhs), (rhs, lhs)] {
+                if let (Some(Term::Value(alias, 0)), Some(term)) =
+                    (parse_term(alias), parse_term(target))
+                {
+                    if !params.contains(&alias.as_str()) && params.contains(&term.name()) {
+                        parser.aliases.insert(alias, term);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut constraints: HashMap<String, Constraints> = HashMap::new();
+    for line in lines {
+        let text = line.as_ref();
+        let (sides, ops) = split_comparisons(text);
+        let sides = sides
+            .iter()
+            .map(|s| parser.parse_side(s))
+            .collect::<Vec<_>>();
+        for (i, op) in ops.iter().enumerate() {
+            let (lhs, rhs) = match (&sides[i], &sides[i + 1]) {
+                (Some(lhs), Some(rhs)) => (lhs, rhs),
+                _ => continue,
+            };
+            // `a < b` is `b > a`, every pair bounds both of its sides
+            for (this, other, op) in [(lhs, rhs, *op), (rhs, lhs, flip(op))] {
+                let Side::Terms(terms) = this else { continue };
+                for (term, offset) in terms {
+                    let bounds = match other {
+                        Side::Number(v) => vec![Bound::Int(v.saturating_sub(*offset))],
+                        Side::Terms(others) => others
+                            .iter()
+                            .filter(|(t, _)| t != term)
+                            .map(|(t, o)| Bound::Ref(t.clone(), o.saturating_sub(*offset)))
+                            .collect(),
+                    };
+                    let range = constraints
+                        .entry(term.name().to_owned())
+                        .or_default()
+                        .range_mut(term);
+                    for bound in bounds {
+                        match op {
+                            "<=" => range.add_max(bound),
+                            "<" => range.add_max(shift(bound, -1)),
+                            ">=" => range.add_min(bound),
+                            ">" => range.add_min(shift(bound, 1)),
+                            "==" => {
+                                range.add_min(bound.clone());
+                                range.add_max(bound);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        let mentioned = WORD_RE
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|w| params.contains(w))
+            .collect::<Vec<_>>();
+        let unique = UNIQUE_RE.is_match(text);
+        let sorted = SORTED_RE.is_match(text) && !DESCENDING_RE.is_match(text);
+        let alphabet = alphabet(text);
+        for name in mentioned {
+            let c = constraints.entry(name.to_owned()).or_default();
+            c.unique |= unique;
+            c.sorted |= sorted;
+            if alphabet.is_some() {
+                c.alphabet = alphabet.clone();
+            }
+        }
+    }
+    constraints
+}
+
+fn flip(op: &str) -> &str {
+    match op {
+        "<=" => ">=",
+        "<" => ">",
+        ">=" => "<=",
+        ">" => "<",
+        op => op,
+    }
+}
+
+fn shift(bound: Bound, by: i64) -> Bound {
+    match bound {
+        Bound::Int(v) => Bound::Int(v.saturating_add(by)),
+        Bound::Ref(term, offset) => Bound::Ref(term, offset.saturating_add(by)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_number() {
+        assert_eq!(eval_number("10^4"), Some(10000));
+        assert_eq!(eval_number("-2^31"), Some(-2147483648));
+        assert_eq!(eval_number("2^31 - 1"), Some(2147483647));
+        assert_eq!(eval_number("5 * 10^4"), Some(50000));
+        assert_eq!(eval_number("nums.length"), None);
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        let html = "<p><strong>提示：</strong></p>\n<ul>\n\t<li><code>2 &lt;= nums.length &lt;= 10<sup>4</sup></code></li>\n\t<li><code>-10<sup>9</sup> &lt;= nums[i] &lt;= 10<sup>9</sup></code></li>\n\t<li><code>1 &lt;= k &lt; nums.length</code></li>\n\t<li><code>costs[i].length == 3</code></li>\n\t<li><code>nums</code> 中的所有整数 <strong>互不相同</strong></li>\n\t<li><code>nums</code> 按 <strong>升序</strong> 排列</li>\n\t<li><code>s</code> 仅由小写英文字母组成</li>\n</ul>";
+        let c = parse_constraints(html, &["nums", "k", "costs", "s"]);
+        let nums = &c["nums"];
+        assert_eq!(nums.length(0).unwrap().int_min(), Some(2));
+        assert_eq!(nums.length(0).unwrap().int_max(), Some(10000));
+        assert_eq!(nums.values.int_min(), Some(-1000000000));
+        assert!(nums.unique && nums.sorted);
+        assert_eq!(
+            c["k"].values.max,
+            vec![Bound::Ref(Term::Length("nums".to_owned(), 0), -1)]
+        );
+        assert_eq!(c["costs"].length(1).unwrap().fixed(), Some(3));
+        assert_eq!(
+            c["s"].alphabet.as_deref(),
+            Some("abcdefghijklmnopqrstuvwxyz")
+        );
+    }
+
+    #[test]
+    fn test_parse_constraints_descending() {
+        let lines = [
+            "grid is sorted in non-increasing order both row-wise and column-wise.",
+            "nums is sorted in descending order",
+            "arr 按非递减顺序排列",
+        ];
+        let c = parse_constraint_lines(&lines, &["grid", "nums", "arr"]);
+        assert!(!c["grid"].sorted);
+        assert!(!c["nums"].sorted);
+        assert!(c["arr"].sorted);
+    }
+
+    #[test]
+    fn test_parse_constraints_saturating() {
+        let c = parse_constraint_lines(&["-10^30 < k - 1 <= 10^30"], &["k"]);
+        assert_eq!(c["k"].values.int_min(), Some(i64::MIN + 2));
+        assert_eq!(c["k"].values.int_max(), Some(i64::MAX));
+    }
+
+    #[test]
+    fn test_parse_constraints_alias() {
+        let html = "<p><strong>Constraints:</strong></p>\n<ul>\n\t<li><code>m == grid.length</code></li>\n\t<li><code>n == grid[i].length</code></li>\n\t<li><code>1 &lt;= m, n &lt;= 300</code></li>\n\t<li><code>grid[i][j]</code> is <code>&#39;0&#39;</code> or <code>&#39;1&#39;</code>.</li>\n</ul>";
+        let c = parse_constraints(html, &["grid"]);
+        let grid = &c["grid"];
+        assert_eq!(grid.length(0).unwrap().int_max(), Some(300));
+        assert_eq!(grid.length(1).unwrap().int_min(), Some(1));
+        assert_eq!(grid.alphabet.as_deref(), Some("01"));
+    }
+}
@@ -3,6 +3,8 @@
 pub struct Question {
     #[serde(rename = "questionId")]
     pub question_id: String,
+    #[serde(rename = "questionFrontendId", default)]
+    pub question_frontend_id: Option<String>,
     #[serde(rename = "title")]
     pub title: String,
     #[serde(rename = "titleSlug")]
@@ -11,6 +13,8 @@ pub struct Question {
     pub code_snippets: Vec<CodeSnippet>,
     #[serde(rename = "translatedTitle")]
     pub translated_title: String,
+    #[serde(default)]
+    pub content: String,
     #[serde(rename = "translatedContent")]
     pub translated_content: String,
     #[serde(rename = "hints")]
@@ -22,6 +26,16 @@ pub struct Question {
     #[serde(rename = "sampleTestCase")]
     pub sample_test_case: String,
     pub difficulty: String,
+    #[serde(rename = "topicTags", default)]
+    pub topic_tags: Vec<TopicTag>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TopicTag {
+    pub name: String,
+    pub slug: String,
+    #[serde(rename = "translatedName")]
+    pub translated_name: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -3,45 +3,194 @@ use std::borrow::Cow;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-fn read_output(line: &str) -> Option<&str> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"输出(:|：)?").unwrap();
+/// One "示例"/"Example" block of the problem statement.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    /// Number shown in the heading, `示例 1` is 1.
+    pub index: usize,
+    /// The input text as shown, e.g. `nums = [2,7,11,15], target = 9`.
+    pub input: String,
+    /// `name = value` pairs of `input`; empty when the input has no names (design problems).
+    pub inputs: Vec<(String, String)>,
+    pub output: Option<String>,
+    pub explanation: Option<String>,
+    pub images: Vec<String>,
+}
+
+impl Example {
+    /// The value of the input named `name`, parsed as JSON.
+    pub fn input_value(&self, name: &str) -> Option<serde_json::Value> {
+        self.inputs
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| serde_json::from_str(v).ok())
+    }
+
+    /// The output parsed as JSON; a bare `#` (level end of next pointer problems) is read as `"#"`.
+    pub fn output_value(&self) -> Option<serde_json::Value> {
+        let output = self.output.as_ref()?;
+        serde_json::from_str(output)
+            .or_else(|_| serde_json::from_str(&output.replace('#', "\"#\"")))
+            .ok()
     }
-    let mut split_iter = RE.split(line);
-    let _ = split_iter.next();
+}
+
+const LABEL_MARK: char = '\u{1}';
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Label {
+    Input,
+    Output,
+    Explanation,
+}
 
-    match split_iter.next() {
-        Some(line) if !line.is_empty() => Some(line.trim()),
+fn parse_label(s: &str) -> Option<Label> {
+    match s.trim().to_lowercase().as_str() {
+        "输入" | "input" => Some(Label::Input),
+        "输出" | "output" => Some(Label::Output),
+        "解释" | "explanation" => Some(Label::Explanation),
         _ => None,
     }
 }
 
-pub fn guest_output(s: &str) -> impl Iterator<Item = Cow<'_, str>> {
-    // lazy_static! {
-    //     static ref RE: Regex = Regex::new(r"输出(:|：)\s?(\S+)").unwrap();
-    // }
+/// Parse the examples of a problem statement, Chinese (`translatedContent`) or English (`content`).
+pub fn parse_examples(html: &str) -> Vec<Example> {
+    lazy_static! {
+        static ref HEAD_RE: Regex =
+            Regex::new(r"<(strong|b)[^>]*>(?:\s|&nbsp;)*(示例|Example)(?:\s|&nbsp;)*(\d*)(?:\s|&nbsp;)*[:：]?(?:\s|&nbsp;)*</(strong|b)>")
+                .unwrap();
+        static ref END_RE: Regex =
+            Regex::new(r"<(strong|b)[^>]*>\s*(提示|Constraints|进阶|Follow)").unwrap();
+        static ref IMG_RE: Regex = Regex::new(r#"<img[^>]*\bsrc\s*=\s*"([^"]+)""#).unwrap();
+    }
 
-    let mut iter = s.split('\n');
-    let mut capture_next = false;
-    (0..).map_while(move |_| loop {
-        match iter.next() {
-            Some(line) if capture_next => {
-                let line = line.trim();
-                if !line.is_empty() {
-                    capture_next = false;
-                    return Some(Cow::Borrowed(line));
-                }
+    let heads = HEAD_RE.captures_iter(html).collect::<Vec<_>>();
+    let mut examples = vec![];
+    for (i, caps) in heads.iter().enumerate() {
+        let whole = caps.get(0).unwrap();
+        let end = match heads.get(i + 1) {
+            Some(next) => next.get(0).unwrap().start(),
+            None => END_RE
+                .find_at(html, whole.end())
+                .map_or(html.len(), |m| m.start()),
+        };
+        let block = &html[whole.end()..end];
+        let mut example = parse_example_block(block);
+        example.index = caps[3].parse().unwrap_or(i + 1);
+        example.images = IMG_RE
+            .captures_iter(block)
+            .map(|c| c[1].to_owned())
+            .collect();
+        examples.push(example);
+    }
+    examples
+}
+
+fn parse_example_block(block: &str) -> Example {
+    lazy_static! {
+        static ref TAG_LABEL_RE: Regex =
+            Regex::new(r"<(strong|b)[^>]*>(?:\s|&nbsp;)*(输入|输出|解释|Input|Output|Explanation)(?:\s|&nbsp;)*[:：]?")
+                .unwrap();
+        static ref LINE_LABEL_RE: Regex =
+            Regex::new(r"(?m)^[ \t]*(输入|输出|解释|Input|Output|Explanation)[ \t]*[:：]").unwrap();
+    }
+    let marked = TAG_LABEL_RE.replace_all(block, format!("{LABEL_MARK}$2{LABEL_MARK}"));
+    let text = html_to_text(&marked);
+    let text = LINE_LABEL_RE.replace_all(&text, format!("{LABEL_MARK}$1{LABEL_MARK}"));
+
+    let mut example = Example::default();
+    let mut parts = text.split(LABEL_MARK).skip(1);
+    while let (Some(label), Some(value)) = (parts.next(), parts.next()) {
+        let value = value.trim();
+        match parse_label(label) {
+            Some(Label::Input) if example.input.is_empty() => {
+                example.input = value.to_owned();
+                example.inputs = parse_named_inputs(value);
+            }
+            Some(Label::Output) if example.output.is_none() => {
+                example.output = Some(join_lines(value));
+            }
+            Some(Label::Explanation) if example.explanation.is_none() => {
+                example.explanation = Some(value.to_owned());
+            }
+            _ => {}
+        }
+    }
+    example
+}
+
+/// A value split over several lines is joined back, `[[1,2],\n [3,4]]` becomes `[[1,2],[3,4]]`.
+fn join_lines(s: &str) -> String {
+    s.lines().map(str::trim).collect::<Vec<_>>().join("")
+}
+
+pub(crate) fn html_to_text(html: &str) -> String {
+    lazy_static! {
+        static ref BREAK_RE: Regex = Regex::new(r"(?i)<br\s*/?>|</p>|</div>").unwrap();
+    }
+    let text = BREAK_RE.replace_all(html, "\n");
+    pure_output(&text)
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Split `nums = [2,7,11,15], target = 9` into named values, ignoring commas inside brackets and strings.
+fn parse_named_inputs(input: &str) -> Vec<(String, String)> {
+    let mut pieces = vec![];
+    let (mut depth, mut in_str, mut escaped, mut start) = (0i32, false, false, 0);
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_str => escaped = true,
+            '"' => in_str = !in_str,
+            '[' | '{' | '(' if !in_str => depth += 1,
+            ']' | '}' | ')' if !in_str => depth -= 1,
+            ',' if !in_str && depth == 0 => {
+                pieces.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    pieces.push(&input[start..]);
+
+    let mut inputs: Vec<(String, String)> = vec![];
+    for piece in pieces {
+        match piece.split_once('=') {
+            Some((name, value)) if is_ident(name.trim()) => {
+                inputs.push((name.trim().to_owned(), value.trim().to_owned()))
             }
-            Some(line) if line.contains("输出") => match read_output(&pure_output(line)) {
-                Some(r) => return Some(r.to_owned().into()),
-                None => {
-                    capture_next = true;
+            // a comma that is part of the value
+            _ => match inputs.last_mut() {
+                Some((_, value)) => {
+                    value.push(',');
+                    value.push_str(piece.trim_end());
                 }
+                None => return vec![],
             },
-            Some(_) => {}
-            None => return None,
         }
-    })
+    }
+    inputs
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether the statement accepts the answer in any order, e.g. "你可以按 **任意顺序** 返回答案".
+pub fn guest_any_order(content: &str) -> bool {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(?i)任意\s*顺序|any\s+order").unwrap();
+    }
+    RE.is_match(&pure_output(content))
 }
 
 fn pure_output(s: &str) -> Cow<'_, str> {
@@ -57,23 +206,86 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_guest_output() {
+    fn test_parse_examples() {
         let content = "<p><strong>示例 1：</strong></p>\n\n<pre>\n<strong>输入: </strong>costs = [[17,2,17],[16,16,5],[14,3,19]]\n<strong>输出: </strong>10\n<strong>解释: </strong>将 0 号房子粉刷成蓝色，1 号房子粉刷成绿色，2 号房子粉刷成蓝色<strong>。</strong>\n&nbsp;    最少花费: 2 + 5 + 3 = 10。\n</pre>\n\n<p><strong>示例 2：</strong></p>\n\n<pre>\n<strong>输入: </strong>costs = [[7,6,2]]\n<strong>输出: 2</strong>\n</pre>\n\n<p>&nbsp;</p>\n\n<p><strong>提示:</strong></p>\n\n<ul>\n\t<li><code>costs.length == n</code></li>\n\t<li><code>costs[i].length == 3</code></li>\n\t<li><code>1 &lt;= n &lt;= 100</code></li>\n\t<li><code>1 &lt;= costs[i][j] &lt;= 20</code></li>\n</ul>\n\n<p>&nbsp;</p>\n\n<p><meta charset=\"UTF-8\" />注意：本题与主站 256&nbsp;题相同：<a href=\"https://leetcode-cn.com/problems/paint-house/\">https://leetcode-cn.com/problems/paint-house/</a></p>\n";
-        let iter = guest_output(content);
-        assert_eq!(iter.collect::<Vec<_>>(), vec!["10", "2"]);
+        let examples = parse_examples(content);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].index, 1);
+        assert_eq!(
+            examples[0].inputs,
+            vec![(
+                "costs".to_owned(),
+                "[[17,2,17],[16,16,5],[14,3,19]]".to_owned()
+            )]
+        );
+        assert_eq!(examples[0].output.as_deref(), Some("10"));
+        assert!(examples[0]
+            .explanation
+            .as_deref()
+            .is_some_and(|e| e.contains("最少花费: 2 + 5 + 3 = 10")));
+        assert_eq!(examples[1].output.as_deref(), Some("2"));
+        assert_eq!(examples[1].explanation, None);
     }
+
     #[test]
-    fn test_guest_output2() {
+    fn test_parse_examples_class() {
         let content = "<p><strong>示例 1：</strong></p>\n\n<pre>\n<strong>输入</strong>\n[\"Solution\", \"pick\", \"pick\", \"pick\", \"pick\", \"pick\", \"pick\", \"pick\"]\n[[7, [2, 3, 5]], [], [], [], [], [], [], []]\n<strong>输出</strong>\n[null, 0, 4, 1, 6, 1, 0, 4]\n\n<b>解释\n</b>Solution solution = new Solution(7, [2, 3, 5]);\nsolution.pick(); // 返回0，任何[0,1,4,6]的整数都可以。注意，对于每一个pick的调用，\n                 // 0、1、4和6的返回概率必须相等(即概率为1/4)。\nsolution.pick(); // 返回 4\nsolution.pick(); // 返回 1\nsolution.pick(); // 返回 6\nsolution.pick(); // 返回 1\nsolution.pick(); // 返回 0\nsolution.pick(); // 返回 4\n</pre>\n\n<p>&nbsp;</p>\n\n<p><strong>提示:</strong></p>\n\n<ul>\n\t<li><code>1 &lt;= n &lt;= 10<sup>9</sup></code></li>\n\t<li><code>0 &lt;= blacklist.length &lt;= min(10<sup>5</sup>, n - 1)</code></li>\n\t<li><code>0 &lt;= blacklist[i] &lt; n</code></li>\n\t<li><code>blacklist</code>&nbsp;中所有值都 <strong>不同</strong></li>\n\t<li>&nbsp;<code>pick</code>&nbsp;最多被调用&nbsp;<code>2 * 10<sup>4</sup></code>&nbsp;次</li>\n</ul>\n";
-        let iter = guest_output(content);
+        let examples = parse_examples(content);
+        assert_eq!(examples.len(), 1);
+        assert!(examples[0].inputs.is_empty());
         assert_eq!(
-            iter.collect::<Vec<_>>(),
-            vec!["[null, 0, 4, 1, 6, 1, 0, 4]"]
+            examples[0].input,
+            "[\"Solution\", \"pick\", \"pick\", \"pick\", \"pick\", \"pick\", \"pick\", \"pick\"]\n[[7, [2, 3, 5]], [], [], [], [], [], [], []]"
         );
+        assert_eq!(
+            examples[0].output.as_deref(),
+            Some("[null, 0, 4, 1, 6, 1, 0, 4]")
+        );
+    }
+
+    #[test]
+    fn test_parse_examples_en() {
+        let content = "<p><strong class=\"example\">Example&nbsp;1:</strong></p>\n<img alt=\"\" src=\"https://assets.leetcode.com/uploads/tree1.jpg\" style=\"width: 400px;\" />\n<pre>\n<strong>Input:</strong> root = [4,2,7], s = &quot;a,b&quot;\n<strong>Output:</strong> [[1,2],\n [3,4]]\n<strong>Explanation:</strong> The Output: is made of pairs.\n</pre>\n\n<p><strong class=\"example\">Example 2:</strong></p>\n<div class=\"example-block\">\n<p><strong>Input:</strong> <span class=\"example-io\">n = 3</span></p>\n<p><strong>Output:</strong> <span class=\"example-io\">true</span></p>\n</div>\n<p>&nbsp;</p>\n<p><strong>Constraints:</strong></p>\n<ul><li><code>1 &lt;= n &lt;= 100</code></li></ul>";
+        let examples = parse_examples(content);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(
+            examples[0].images,
+            vec!["https://assets.leetcode.com/uploads/tree1.jpg"]
+        );
+        assert_eq!(examples[0].input_value("s"), Some(serde_json::json!("a,b")));
+        assert_eq!(
+            examples[0].output_value(),
+            Some(serde_json::json!([[1, 2], [3, 4]]))
+        );
+        assert_eq!(
+            examples[0].explanation.as_deref(),
+            Some("The Output: is made of pairs.")
+        );
+        assert_eq!(examples[1].input_value("n"), Some(serde_json::json!(3)));
+        let next = Example {
+            output: Some("[1,#,2,3,#]".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            next.output_value(),
+            Some(serde_json::json!([1, "#", 2, 3, "#"]))
+        );
+        assert_eq!(examples[1].output.as_deref(), Some("true"));
+    }
+
+    #[test]
+    fn test_guest_any_order() {
+        assert!(guest_any_order(
+            "<p>你可以按 <strong>任意顺序</strong> 返回答案。</p>"
+        ));
+        assert!(guest_any_order(
+            "<p>You can return the answer in <strong>any order</strong>.</p>"
+        ));
+        assert!(!guest_any_order("<p>返回其层序遍历结果。</p>"));
     }
 
     // #[test]
-    // fn test_guest_output3() {
+    // fn test_parse_examples3() {
     //     use crate::fetch::{QuestionWrapper, Response};
     //     let content = include_str!("../.backup/my-calendar-iii.json");
     //     let json: Response<QuestionWrapper> = serde_json::from_str(content).unwrap();
@@ -81,7 +293,7 @@ mod tests {
     //         "--> translated_content: {:?}",
     //         json.data.question.translated_content
     //     );
-    //     let iter = guest_output(&json.data.question.translated_content);
-    //     assert_eq!(iter.collect::<Vec<_>>(), vec!["[null, 1, 1, 2, 3, 3, 3]"]);
+    //     let examples = parse_examples(&json.data.question.translated_content);
+    //     assert_eq!(examples[0].output.as_deref(), Some("[null, 1, 1, 2, 3, 3, 3]"));
     // }
 }
@@ -0,0 +1,239 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{bail, Result};
+use async_std::{fs, process::Command};
+
+pub const LAYOUT_ENV: &str = "LEETCODE_LAYOUT";
+
+const DIFFICULTIES: [&str; 3] = ["easy", "medium", "hard"];
+
+/// Where solution files live inside the project.
+///
+/// - `flat`: `src/<title>.rs`, declared in `src/lib.rs`
+/// - `problems`: `src/problems/<title>.rs`, declared in `src/problems/mod.rs`
+/// - `difficulty`: `src/<easy|medium|hard>/<title>.rs`, declared in `src/<difficulty>/mod.rs`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    #[default]
+    Flat,
+    Problems,
+    Difficulty,
+}
+
+impl FromStr for Layout {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "flat" => Ok(Layout::Flat),
+            "problems" => Ok(Layout::Problems),
+            "difficulty" => Ok(Layout::Difficulty),
+            _ => bail!("unknown layout `{s}`, expected one of `flat`, `problems`, `difficulty`"),
+        }
+    }
+}
+
+impl Layout {
+    /// Read the layout from `LEETCODE_LAYOUT`, defaulting to `flat`.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(LAYOUT_ENV) {
+            Ok(s) => s.parse(),
+            Err(_) => Ok(Layout::default()),
+        }
+    }
+
+    fn module_dir(&self, difficulty: &str) -> Option<String> {
+        match self {
+            Layout::Flat => None,
+            Layout::Problems => Some("problems".to_owned()),
+            Layout::Difficulty => {
+                let d = difficulty.trim().to_lowercase();
+                if DIFFICULTIES.contains(&d.as_str()) {
+                    Some(d)
+                } else {
+                    Some("unknown".to_owned())
+                }
+            }
+        }
+    }
+
+    pub fn solution_path(&self, project_dir: &Path, title: &str, difficulty: &str) -> PathBuf {
+        let mut path = project_dir.join("src");
+        if let Some(dir) = self.module_dir(difficulty) {
+            path.push(dir);
+        }
+        path.push(format!("{title}.rs"));
+        path
+    }
+}
+
+/// Find an existing solution file for `title` in any of the known layouts.
+pub fn locate_solution(project_dir: &Path, title: &str) -> Option<PathBuf> {
+    let src = project_dir.join("src");
+    std::iter::once(src.clone())
+        .chain(
+            ["problems", "unknown"]
+                .iter()
+                .chain(DIFFICULTIES.iter())
+                .map(|dir| src.join(dir)),
+        )
+        .map(|dir| dir.join(format!("{title}.rs")))
+        .find(|p| p.exists())
+}
+
+/// The file declaring the module of a solution file: `src/lib.rs` or the `mod.rs` next to it.
+fn parent_module_file(project_dir: &Path, file: &Path) -> Result<PathBuf> {
+    let src = project_dir.join("src");
+    match file.parent() {
+        Some(p) if p == src => Ok(src.join("lib.rs")),
+        Some(p) => Ok(p.join("mod.rs")),
+        None => bail!("invalid solution path {}", file.display()),
+    }
+}
+
+fn module_name(file: &Path) -> Result<&str> {
+    match file.file_stem().and_then(|s| s.to_str()) {
+        Some(s) => Ok(s),
+        None => bail!("invalid solution path {}", file.display()),
+    }
+}
+
+/// Whether `line` declares module `name`, either `pub mod name;` or `mod name;`.
+fn is_mod_decl(line: &str, name: &str) -> bool {
+    let line = line.trim();
+    let line = line.strip_prefix("pub ").unwrap_or(line).trim_start();
+    line.strip_prefix("mod ")
+        .and_then(|rest| rest.trim().strip_suffix(';'))
+        .is_some_and(|rest| rest.trim() == name)
+}
+
+pub fn add_mod_decl(content: &str, name: &str) -> String {
+    if content.lines().any(|line| is_mod_decl(line, name)) {
+        return content.to_owned();
+    }
+    let decl = format!("pub mod {name};");
+    let mut content = content.to_owned();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&decl);
+    content.push('\n');
+    content
+}
+
+pub fn remove_mod_decl(content: &str, name: &str) -> String {
+    content
+        .lines()
+        .filter(|line| !is_mod_decl(line, name))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+async fn read_or_empty(path: &Path) -> Result<String> {
+    if path.exists() {
+        Ok(fs::read_to_string(path).await?)
+    } else {
+        Ok(String::new())
+    }
+}
+
+async fn declare_module(mod_file: &Path, name: &str) -> Result<()> {
+    let content = read_or_empty(mod_file).await?;
+    fs::write(mod_file, add_mod_decl(&content, name)).await?;
+    Ok(())
+}
+
+/// Declare the module of `file` in its parent module, creating `mod.rs` and its `lib.rs` entry if needed.
+pub async fn register_module(project_dir: &Path, file: &Path) -> Result<()> {
+    let mod_file = parent_module_file(project_dir, file)?;
+    declare_module(&mod_file, module_name(file)?).await?;
+
+    if mod_file.file_name().is_some_and(|n| n == "mod.rs") {
+        let dir = mod_file.parent().unwrap();
+        declare_module(&project_dir.join("src/lib.rs"), module_name(dir)?).await?;
+    }
+    Ok(())
+}
+
+/// `git rm` the file so its deletion is staged next to the `git mv`s of `migrate`.
+async fn git_rm(project_dir: &Path, file: &Path) -> Result<()> {
+    let status = Command::new("git")
+        .args(["rm", "-q", "-f"])
+        .arg(file)
+        .current_dir(project_dir)
+        .status()
+        .await;
+    match status {
+        Ok(s) if s.success() => {}
+        _ => {
+            log::warn!("`git rm {}` failed, fall back to remove", file.display());
+            fs::remove_file(file).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove the declaration of `file`, dropping its directory module once it is empty.
+pub async fn unregister_module(project_dir: &Path, file: &Path) -> Result<()> {
+    let mod_file = parent_module_file(project_dir, file)?;
+    if !mod_file.exists() {
+        return Ok(());
+    }
+    let content = remove_mod_decl(&fs::read_to_string(&mod_file).await?, module_name(file)?);
+    let is_dir_module = mod_file.file_name().is_some_and(|n| n == "mod.rs");
+    if is_dir_module && content.trim().is_empty() {
+        git_rm(project_dir, &mod_file).await?;
+        let dir = mod_file.parent().unwrap();
+        let _ = fs::remove_dir(dir).await;
+        let lib_file = project_dir.join("src/lib.rs");
+        let lib_content = fs::read_to_string(&lib_file).await?;
+        fs::write(&lib_file, remove_mod_decl(&lib_content, module_name(dir)?)).await?;
+    } else {
+        fs::write(&mod_file, content).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solution_path() {
+        let dir = Path::new("/tmp/p");
+        assert_eq!(
+            Layout::Flat.solution_path(dir, "two_sum", "Easy"),
+            Path::new("/tmp/p/src/two_sum.rs")
+        );
+        assert_eq!(
+            Layout::Problems.solution_path(dir, "two_sum", "Easy"),
+            Path::new("/tmp/p/src/problems/two_sum.rs")
+        );
+        assert_eq!(
+            Layout::Difficulty.solution_path(dir, "two_sum", "Easy"),
+            Path::new("/tmp/p/src/easy/two_sum.rs")
+        );
+    }
+
+    #[test]
+    fn test_mod_decl() {
+        let content = "pub mod a;\npub mod b;";
+        let content = add_mod_decl(content, "c");
+        assert_eq!(content, "pub mod a;\npub mod b;\npub mod c;\n");
+        assert_eq!(add_mod_decl(&content, "a"), content);
+        assert_eq!(remove_mod_decl(&content, "b"), "pub mod a;\npub mod c;\n");
+
+        let content = "mod a;\npub mod ab;\n";
+        assert_eq!(add_mod_decl(content, "a"), content);
+        assert_eq!(remove_mod_decl(content, "a"), "pub mod ab;\n");
+    }
+
+    #[test]
+    fn test_parse_layout() {
+        assert_eq!("Problems".parse::<Layout>().unwrap(), Layout::Problems);
+        assert!("tree".parse::<Layout>().is_err());
+    }
+}
@@ -67,6 +67,7 @@ pub async fn submit(question: &Question, code: &str, cookie: &str) -> Result<Sub
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "state")]
+#[allow(clippy::large_enum_variant)]
 pub enum CheckSubmissionsResponse {
     STARTED,
     PENDING,
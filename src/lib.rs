@@ -1,9 +1,16 @@
+// lets `#[test_object]` expand to `::leetcode_tool::...` inside this crate
+extern crate self as leetcode_tool;
+
+pub mod constraint;
 pub mod domain;
 pub mod errors;
 pub mod fetch;
 pub mod guest;
+pub mod layout;
 pub mod leetcode;
 pub mod meta;
+pub mod migrate;
+pub mod perf;
 pub mod submit;
 pub mod template;
 pub mod testcase;
@@ -12,10 +19,28 @@ pub mod util;
 mod libs;
 
 pub mod prelude {
-    pub use super::libs::list::ListNode;
-    pub use super::libs::test::{assert_object, TestObject};
-    pub use super::libs::tree::TreeNode;
+    pub use super::libs::interactive::{
+        api_calls, guess, set_bad_version, set_guess_pick, ArrayReader, BadVersionApi, Calls,
+        HtmlParser, Master, MountainArray, Robot,
+    };
+    pub use super::libs::json::{FromLeetcodeJson, ToLeetcodeJson};
+    pub use super::libs::list::{
+        build_cycle_list, build_intersection_lists, build_list, same_node, shared_list_index,
+        ListNode, SharedListNode,
+    };
+    pub use super::libs::memory::{measure_memory, CountingAlloc, MemoryUsage};
+    pub use super::libs::nested::{Interval, NestedInteger};
+    pub use super::libs::node::{GraphNode, NaryNode, NextNode, QuadNode, RandomNode};
+    pub use super::libs::perf::{bench, perf};
+    pub use super::libs::stress::{stress, InputGen, Shape};
+    pub use super::libs::test::{
+        approx_eq, assert_object, load_testcases, unordered_eq, Checker, TestCase, TestObject,
+    };
+    pub use super::libs::tree::{build_tree, format_tree, RawTree, TreeNode};
+    pub use crate::{list, tree};
+    pub use leetcode_tool_derive::test_object;
     pub use rand;
     pub use serde_json;
     pub use serde_json::{json, Value};
+    pub use std::{cell::RefCell, rc::Rc};
 }
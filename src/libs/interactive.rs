@@ -0,0 +1,317 @@
+//! Local stand-ins for the judge APIs of interactive problems. Each counts the calls made to
+//! it and panics once the judge's limit is exceeded.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+};
+
+/// A call counter with an optional limit.
+#[derive(Debug, Default)]
+pub struct Calls {
+    count: Cell<usize>,
+    limit: Option<usize>,
+}
+
+impl Calls {
+    pub fn with_limit(limit: usize) -> Self {
+        Calls {
+            count: Cell::new(0),
+            limit: Some(limit),
+        }
+    }
+
+    fn hit(&self, api: &str) {
+        let count = self.count.get() + 1;
+        self.count.set(count);
+        if let Some(limit) = self.limit {
+            assert!(count <= limit, "`{api}` called more than {limit} times");
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count.get()
+    }
+}
+
+thread_local! {
+    static PICK: Cell<i32> = const { Cell::new(0) };
+    static BAD_VERSION: Cell<i32> = const { Cell::new(0) };
+    static CALLS: RefCell<HashMap<&'static str, usize>> = RefCell::new(HashMap::new());
+}
+
+fn count_call(api: &'static str) {
+    CALLS.with(|calls| *calls.borrow_mut().entry(api).or_default() += 1);
+}
+
+/// How many times the free API `api` (`guess`, `isBadVersion`) was called on this thread.
+pub fn api_calls(api: &str) -> usize {
+    CALLS.with(|calls| calls.borrow().get(api).copied().unwrap_or_default())
+}
+
+/// Guess number higher or lower: set the picked number before calling the solution.
+pub fn set_guess_pick(pick: i32) {
+    PICK.with(|p| p.set(pick));
+    CALLS.with(|calls| calls.borrow_mut().remove("guess"));
+}
+
+/// # Safety
+/// Declared `unsafe` like the judge's API, it is safe to call.
+pub unsafe fn guess(num: i32) -> i32 {
+    count_call("guess");
+    let pick = PICK.with(Cell::get);
+    (pick - num).signum()
+}
+
+/// First bad version: set the first bad version before calling the solution.
+pub fn set_bad_version(bad: i32) {
+    BAD_VERSION.with(|b| b.set(bad));
+    CALLS.with(|calls| calls.borrow_mut().remove("isBadVersion"));
+}
+
+/// `self.isBadVersion(version)` of the first bad version problem, implement it for `Solution`.
+#[allow(non_snake_case)]
+pub trait BadVersionApi {
+    fn isBadVersion(&self, version: i32) -> bool {
+        count_call("isBadVersion");
+        version >= BAD_VERSION.with(Cell::get)
+    }
+}
+
+/// Find in mountain array, at most 100 calls to `get`.
+#[derive(Debug)]
+pub struct MountainArray {
+    values: Vec<i32>,
+    pub calls: Calls,
+}
+
+impl MountainArray {
+    pub fn new(values: Vec<i32>) -> Self {
+        MountainArray {
+            values,
+            calls: Calls::with_limit(100),
+        }
+    }
+
+    pub fn get(&self, index: i32) -> i32 {
+        self.calls.hit("MountainArray.get");
+        self.values[index as usize]
+    }
+
+    pub fn length(&self) -> i32 {
+        self.values.len() as i32
+    }
+}
+
+/// Search in a sorted array of unknown size, `get` is `i32::MAX` out of bounds.
+#[derive(Debug)]
+pub struct ArrayReader {
+    values: Vec<i32>,
+    pub calls: Calls,
+}
+
+impl ArrayReader {
+    pub fn new(values: Vec<i32>) -> Self {
+        ArrayReader {
+            values,
+            calls: Calls::default(),
+        }
+    }
+
+    pub fn get(&self, index: i32) -> i32 {
+        self.calls.hit("ArrayReader.get");
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.values.get(i))
+            .copied()
+            .unwrap_or(i32::MAX)
+    }
+}
+
+/// Guess the word, at most `allowed_guesses` calls to `guess`.
+#[derive(Debug)]
+pub struct Master {
+    secret: String,
+    words: HashSet<String>,
+    guessed: Cell<bool>,
+    pub calls: Calls,
+}
+
+impl Master {
+    pub fn new(secret: String, words: Vec<String>, allowed_guesses: i32) -> Self {
+        Master {
+            secret,
+            words: words.into_iter().collect(),
+            guessed: Cell::new(false),
+            calls: Calls::with_limit(allowed_guesses as usize),
+        }
+    }
+
+    /// Number of exact matches, `-1` for a word not in the list.
+    pub fn guess(&self, word: String) -> i32 {
+        self.calls.hit("Master.guess");
+        if !self.words.contains(&word) {
+            return -1;
+        }
+        if word == self.secret {
+            self.guessed.set(true);
+        }
+        word.bytes()
+            .zip(self.secret.bytes())
+            .filter(|(a, b)| a == b)
+            .count() as i32
+    }
+
+    /// Whether the secret was guessed within the allowed guesses.
+    pub fn guessed(&self) -> bool {
+        self.guessed.get()
+    }
+}
+
+/// Web crawler: `edges[i] = [from, to]` are links between `urls`.
+#[derive(Debug)]
+pub struct HtmlParser {
+    links: HashMap<String, Vec<String>>,
+    pub calls: Calls,
+}
+
+impl HtmlParser {
+    pub fn new(urls: Vec<String>, edges: Vec<Vec<i32>>) -> Self {
+        let mut links: HashMap<String, Vec<String>> = HashMap::new();
+        for edge in edges {
+            let (from, to) = (&urls[edge[0] as usize], &urls[edge[1] as usize]);
+            links.entry(from.clone()).or_default().push(to.clone());
+        }
+        HtmlParser {
+            links,
+            calls: Calls::default(),
+        }
+    }
+
+    pub fn get_urls(&self, url: String) -> Vec<String> {
+        self.calls.hit("HtmlParser.getUrls");
+        self.links.get(&url).cloned().unwrap_or_default()
+    }
+}
+
+/// Robot room cleaner: `room[i][j]` is 1 for an open cell, the robot starts at (`row`, `col`)
+/// facing up.
+#[derive(Debug)]
+pub struct Robot {
+    room: Vec<Vec<i32>>,
+    position: Cell<(i32, i32)>,
+    direction: Cell<usize>,
+    cleaned: RefCell<HashSet<(i32, i32)>>,
+    pub calls: Calls,
+}
+
+impl Robot {
+    const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+    pub fn new(room: Vec<Vec<i32>>, row: i32, col: i32) -> Self {
+        Robot {
+            room,
+            position: Cell::new((row, col)),
+            direction: Cell::new(0),
+            cleaned: RefCell::new(HashSet::new()),
+            calls: Calls::default(),
+        }
+    }
+
+    fn is_open(&self, (row, col): (i32, i32)) -> bool {
+        usize::try_from(row)
+            .ok()
+            .zip(usize::try_from(col).ok())
+            .and_then(|(r, c)| self.room.get(r)?.get(c))
+            == Some(&1)
+    }
+
+    /// Move a cell forward, `false` when blocked.
+    pub fn r#move(&self) -> bool {
+        self.calls.hit("Robot.move");
+        let (row, col) = self.position.get();
+        let (dr, dc) = Self::DIRECTIONS[self.direction.get()];
+        let next = (row + dr, col + dc);
+        let open = self.is_open(next);
+        if open {
+            self.position.set(next);
+        }
+        open
+    }
+
+    pub fn turn_left(&self) {
+        self.calls.hit("Robot.turnLeft");
+        self.direction.set((self.direction.get() + 3) % 4);
+    }
+
+    pub fn turn_right(&self) {
+        self.calls.hit("Robot.turnRight");
+        self.direction.set((self.direction.get() + 1) % 4);
+    }
+
+    pub fn clean(&self) {
+        self.calls.hit("Robot.clean");
+        self.cleaned.borrow_mut().insert(self.position.get());
+    }
+
+    /// Whether every open cell was cleaned.
+    pub fn all_cleaned(&self) -> bool {
+        let cleaned = self.cleaned.borrow();
+        self.room.iter().enumerate().all(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .all(|(c, &cell)| cell != 1 || cleaned.contains(&(r as i32, c as i32)))
+        })
+    }
+}
+
+#[test]
+fn guess_number() {
+    set_guess_pick(6);
+    assert_eq!(unsafe { guess(10) }, -1);
+    assert_eq!(unsafe { guess(6) }, 0);
+    assert_eq!(api_calls("guess"), 2);
+
+    struct Solution;
+    impl BadVersionApi for Solution {}
+    set_bad_version(4);
+    assert!(!Solution.isBadVersion(3));
+    assert!(Solution.isBadVersion(5));
+    assert_eq!(api_calls("isBadVersion"), 2);
+}
+
+#[test]
+#[should_panic(expected = "`MountainArray.get` called more than 100 times")]
+fn mountain_limit() {
+    let arr = MountainArray::new(vec![1, 2, 3, 4, 5, 3, 1]);
+    for _ in 0..101 {
+        arr.get(0);
+    }
+}
+
+#[test]
+fn master() {
+    let words = ["acckzz", "ccbazz", "eiowzz", "abcczz"].map(String::from);
+    let master = Master::new("acckzz".to_owned(), words.to_vec(), 10);
+    assert_eq!(master.guess("aaaaaa".to_owned()), -1);
+    assert_eq!(master.guess("ccbazz".to_owned()), 3);
+    assert!(!master.guessed());
+    assert_eq!(master.guess("acckzz".to_owned()), 6);
+    assert!(master.guessed());
+    assert_eq!(master.calls.count(), 3);
+}
+
+#[test]
+fn robot() {
+    let robot = Robot::new(vec![vec![1, 1], vec![0, 1]], 0, 0);
+    robot.clean();
+    assert!(!robot.r#move());
+    robot.turn_right();
+    assert!(robot.r#move());
+    robot.clean();
+    robot.turn_right();
+    assert!(robot.r#move());
+    assert!(!robot.all_cleaned());
+    robot.clean();
+    assert!(robot.all_cleaned());
+}
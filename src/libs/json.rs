@@ -0,0 +1,223 @@
+use std::{cell::RefCell, rc::Rc};
+
+use serde_json::Value;
+
+use super::{
+    list::{build_list, ListNode},
+    nested::{Interval, NestedInteger},
+    node::{GraphNode, NaryNode, NextNode, QuadNode, RandomNode},
+    tree::{level_order, TreeNode},
+};
+
+/// Build a parameter or expected value from its LeetCode JSON form.
+pub trait FromLeetcodeJson: Sized {
+    fn from_leetcode_json(value: &Value) -> Self;
+}
+
+/// Serialize a result into LeetCode's JSON form, e.g. a `ListNode` as `[1,2,3]`.
+pub trait ToLeetcodeJson {
+    fn to_leetcode_json(&self) -> Value;
+}
+
+impl FromLeetcodeJson for i32 {
+    fn from_leetcode_json(value: &Value) -> Self {
+        i32::try_from(value.as_i64().expect("expect integer")).expect("integer out of i32 range")
+    }
+}
+
+impl FromLeetcodeJson for i64 {
+    fn from_leetcode_json(value: &Value) -> Self {
+        value.as_i64().expect("expect integer")
+    }
+}
+
+impl FromLeetcodeJson for f64 {
+    fn from_leetcode_json(value: &Value) -> Self {
+        value.as_f64().expect("expect number")
+    }
+}
+
+impl FromLeetcodeJson for bool {
+    fn from_leetcode_json(value: &Value) -> Self {
+        value.as_bool().expect("expect boolean")
+    }
+}
+
+impl FromLeetcodeJson for String {
+    fn from_leetcode_json(value: &Value) -> Self {
+        value.as_str().expect("expect string").to_owned()
+    }
+}
+
+impl FromLeetcodeJson for char {
+    fn from_leetcode_json(value: &Value) -> Self {
+        value
+            .as_str()
+            .and_then(|s| s.chars().next())
+            .expect("expect character")
+    }
+}
+
+impl<T: FromLeetcodeJson> FromLeetcodeJson for Vec<T> {
+    fn from_leetcode_json(value: &Value) -> Self {
+        value
+            .as_array()
+            .expect("expect array")
+            .iter()
+            .map(T::from_leetcode_json)
+            .collect()
+    }
+}
+
+impl FromLeetcodeJson for Option<Box<ListNode>> {
+    fn from_leetcode_json(value: &Value) -> Self {
+        ListNode::from_jsonstr(&value.to_string())
+    }
+}
+
+impl FromLeetcodeJson for Option<Rc<RefCell<TreeNode>>> {
+    fn from_leetcode_json(value: &Value) -> Self {
+        TreeNode::from_jsonstr(&value.to_string())
+    }
+}
+
+/// The `Node` types, read and written through their `from_jsonstr` / `to_value`.
+macro_rules! node_json {
+    ($($t:ty),*) => {
+        $(
+            impl FromLeetcodeJson for Option<Rc<RefCell<$t>>> {
+                fn from_leetcode_json(value: &Value) -> Self {
+                    <$t>::from_jsonstr(&value.to_string())
+                }
+            }
+
+            impl ToLeetcodeJson for Option<Rc<RefCell<$t>>> {
+                fn to_leetcode_json(&self) -> Value {
+                    <$t>::to_value(self)
+                }
+            }
+        )*
+    };
+}
+
+node_json!(GraphNode, RandomNode, NaryNode, NextNode, QuadNode);
+
+impl FromLeetcodeJson for NestedInteger {
+    fn from_leetcode_json(value: &Value) -> Self {
+        NestedInteger::from_value(value)
+    }
+}
+
+impl ToLeetcodeJson for NestedInteger {
+    fn to_leetcode_json(&self) -> Value {
+        self.to_value()
+    }
+}
+
+impl FromLeetcodeJson for Interval {
+    fn from_leetcode_json(value: &Value) -> Self {
+        let [start, end] = <[i32; 2]>::try_from(Vec::<i32>::from_leetcode_json(value))
+            .expect("expect [start, end]");
+        Interval::new(start, end)
+    }
+}
+
+impl ToLeetcodeJson for Interval {
+    fn to_leetcode_json(&self) -> Value {
+        Value::from(vec![self.start, self.end])
+    }
+}
+
+macro_rules! to_json_via_from {
+    ($($t:ty),*) => {
+        $(
+            impl ToLeetcodeJson for $t {
+                fn to_leetcode_json(&self) -> Value {
+                    Value::from(self.clone())
+                }
+            }
+        )*
+    };
+}
+
+to_json_via_from!(i32, i64, f64, bool, String);
+
+impl ToLeetcodeJson for char {
+    fn to_leetcode_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl ToLeetcodeJson for &str {
+    fn to_leetcode_json(&self) -> Value {
+        Value::String((*self).to_owned())
+    }
+}
+
+impl<T: ToLeetcodeJson> ToLeetcodeJson for Vec<T> {
+    fn to_leetcode_json(&self) -> Value {
+        Value::Array(self.iter().map(T::to_leetcode_json).collect())
+    }
+}
+
+impl ToLeetcodeJson for Option<Box<ListNode>> {
+    fn to_leetcode_json(&self) -> Value {
+        Value::from(build_list(self.clone()))
+    }
+}
+
+impl ToLeetcodeJson for Option<Rc<RefCell<TreeNode>>> {
+    fn to_leetcode_json(&self) -> Value {
+        Value::from(level_order(self))
+    }
+}
+
+#[test]
+fn from_leetcode_json() {
+    let v: Vec<Vec<String>> =
+        FromLeetcodeJson::from_leetcode_json(&serde_json::json!([["a", "b"], []]));
+    assert_eq!(v, vec![vec!["a".to_owned(), "b".to_owned()], vec![]]);
+
+    let list: Option<Box<ListNode>> =
+        FromLeetcodeJson::from_leetcode_json(&serde_json::json!([1, 2]));
+    assert_eq!(list, ListNode::from_iter(vec![1, 2]));
+}
+
+#[test]
+#[should_panic(expected = "integer out of i32 range")]
+fn from_leetcode_json_overflow() {
+    let _: i32 = FromLeetcodeJson::from_leetcode_json(&serde_json::json!(2147483648i64));
+}
+
+#[test]
+fn node_json() {
+    let intervals: Vec<Interval> =
+        FromLeetcodeJson::from_leetcode_json(&serde_json::json!([[1, 3], [6, 9]]));
+    assert_eq!(intervals[1], Interval::new(6, 9));
+    assert_eq!(
+        intervals.to_leetcode_json(),
+        serde_json::json!([[1, 3], [6, 9]])
+    );
+
+    let graph: Option<Rc<RefCell<GraphNode>>> =
+        FromLeetcodeJson::from_leetcode_json(&serde_json::json!([[2], [1]]));
+    assert_eq!(graph.to_leetcode_json(), serde_json::json!([[2], [1]]));
+}
+
+#[test]
+fn to_leetcode_json() {
+    assert_eq!(
+        vec![vec!['a'], vec![]].to_leetcode_json(),
+        serde_json::json!([["a"], []])
+    );
+
+    let tree = TreeNode::from_jsonstr("[3,9,20,null,null,15,7]");
+    assert_eq!(
+        tree.to_leetcode_json(),
+        serde_json::json!([3, 9, 20, null, null, 15, 7])
+    );
+    assert_eq!(
+        vec![ListNode::from_iter(vec![1, 2]), None].to_leetcode_json(),
+        serde_json::json!([[1, 2], []])
+    );
+}
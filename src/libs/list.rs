@@ -1,3 +1,5 @@
+use std::{cell::RefCell, rc::Rc};
+
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct ListNode {
     pub val: i32,
@@ -32,6 +34,7 @@ impl ListNode {
         })
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_iter<I: IntoIterator<Item = i32>>(list: I) -> Option<Box<ListNode>> {
         let mut iter = list.into_iter();
         match iter.next() {
@@ -44,7 +47,7 @@ impl ListNode {
         }
     }
     pub fn from_iter_rev<I: IntoIterator<Item = i32>>(list: I) -> Option<Box<ListNode>> {
-        let mut iter = list.into_iter();
+        let iter = list.into_iter();
         let mut root: Option<Box<ListNode>> = None;
         for val in iter {
             root = Some(Box::new(ListNode { val, next: root }));
@@ -57,12 +60,59 @@ impl ListNode {
     }
 }
 
+impl ListNode {
+    pub fn from_vec(values: Vec<i32>) -> Option<Box<ListNode>> {
+        Self::from_iter(values)
+    }
+
+    /// Values from this node on.
+    pub fn to_vec(&self) -> Vec<i32> {
+        let mut list = vec![self.val];
+        let mut cur = &self.next;
+        while let Some(c) = cur {
+            list.push(c.val);
+            cur = &c.next;
+        }
+        list
+    }
+}
+
 impl std::fmt::Display for ListNode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", build_list(Some(Box::new(self.clone()))))
+        write!(f, "{:?}", self.to_vec())
+    }
+}
+
+impl From<&ListNode> for Vec<i32> {
+    fn from(node: &ListNode) -> Self {
+        node.to_vec()
+    }
+}
+
+impl serde::Serialize for ListNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_vec().serialize(serializer)
     }
 }
 
+/// From the array form, the list can not be empty.
+impl<'de> serde::Deserialize<'de> for ListNode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<i32>::deserialize(deserializer)?;
+        Self::from_vec(values)
+            .map(|node| *node)
+            .ok_or_else(|| serde::de::Error::custom("empty list"))
+    }
+}
+
+/// `list![1, 2, 3]`, `None` for `list![]`.
+#[macro_export]
+macro_rules! list {
+    ($($x:expr),* $(,)?) => {
+        $crate::prelude::ListNode::from_vec(vec![$($x),*])
+    };
+}
+
 pub fn build_list(node: Option<Box<ListNode>>) -> Vec<i32> {
     let mut list = vec![];
     let mut cur = &node;
@@ -72,3 +122,148 @@ pub fn build_list(node: Option<Box<ListNode>>) -> Vec<i32> {
     }
     list
 }
+
+#[test]
+fn list_utils() {
+    let list = list![1, 2, 3];
+    assert_eq!(list, ListNode::from_jsonstr("[1,2,3]"));
+    let node = list.unwrap();
+    assert_eq!(node.to_vec(), vec![1, 2, 3]);
+    assert_eq!(node.to_string(), "[1, 2, 3]");
+    assert_eq!(Vec::from(&*node), vec![1, 2, 3]);
+
+    let s = serde_json::to_string(&node).unwrap();
+    assert_eq!(s, "[1,2,3]");
+    assert_eq!(serde_json::from_str::<ListNode>(&s).unwrap(), *node);
+    assert!(serde_json::from_str::<ListNode>("[]").is_err());
+    assert_eq!(list![], None);
+}
+
+/// A list node that can be shared, for the cycle and intersection problems `Box` can not express.
+pub struct SharedListNode {
+    pub val: i32,
+    pub next: Option<Rc<RefCell<SharedListNode>>>,
+}
+
+pub type SharedList = Option<Rc<RefCell<SharedListNode>>>;
+
+impl SharedListNode {
+    #[inline]
+    pub fn new(val: i32) -> Self {
+        SharedListNode { val, next: None }
+    }
+}
+
+fn build_shared_nodes(values: &[i32]) -> Vec<Rc<RefCell<SharedListNode>>> {
+    let nodes = values
+        .iter()
+        .map(|&val| Rc::new(RefCell::new(SharedListNode::new(val))))
+        .collect::<Vec<_>>();
+    for pair in nodes.windows(2) {
+        pair[0].borrow_mut().next = Some(pair[1].clone());
+    }
+    nodes
+}
+
+/// `head = [3,2,0,-4], pos = 1`: the tail links back to the node at index `pos`, no cycle when `pos` is -1.
+pub fn build_cycle_list(values: Vec<i32>, pos: i32) -> SharedList {
+    let nodes = build_shared_nodes(&values);
+    if let (Some(tail), Ok(pos)) = (nodes.last(), usize::try_from(pos)) {
+        tail.borrow_mut().next = nodes.get(pos).cloned();
+    }
+    nodes.first().cloned()
+}
+
+/// `intersectVal, listA, listB, skipA, skipB`: list B links to `listA[skipA]` after its first `skipB`
+/// nodes, returned with the heads as the expected intersection node (`None` when `intersectVal` is 0).
+pub fn build_intersection_lists(
+    intersect_val: i32,
+    list_a: Vec<i32>,
+    list_b: Vec<i32>,
+    skip_a: i32,
+    skip_b: i32,
+) -> (SharedList, SharedList, SharedList) {
+    let skip_a = usize::try_from(skip_a).expect("skipA must not be negative");
+    let skip_b = usize::try_from(skip_b).expect("skipB must not be negative");
+    let nodes_a = build_shared_nodes(&list_a);
+    let intersection = match intersect_val {
+        0 => None,
+        _ => nodes_a.get(skip_a).cloned(),
+    };
+    let nodes_b = match intersection {
+        Some(_) => build_shared_nodes(&list_b[..skip_b.min(list_b.len())]),
+        None => build_shared_nodes(&list_b),
+    };
+    let head_b = match nodes_b.last() {
+        Some(tail) => {
+            if intersection.is_some() {
+                tail.borrow_mut().next = intersection.clone();
+            }
+            nodes_b.first().cloned()
+        }
+        None => intersection.clone(),
+    };
+    (nodes_a.first().cloned(), head_b, intersection)
+}
+
+/// Whether both are the same node (or both `None`).
+pub fn same_node(a: &SharedList, b: &SharedList) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Index of `node` in the list starting at `head`, following at most one lap of a cycle.
+pub fn shared_list_index(head: &SharedList, node: &SharedList) -> Option<usize> {
+    let node = node.as_ref()?;
+    let mut seen: Vec<Rc<RefCell<SharedListNode>>> = vec![];
+    let mut cur = head.clone();
+    while let Some(n) = cur {
+        if Rc::ptr_eq(&n, node) {
+            return Some(seen.len());
+        }
+        if seen.iter().any(|s| Rc::ptr_eq(s, &n)) {
+            return None;
+        }
+        cur = n.borrow().next.clone();
+        seen.push(n);
+    }
+    None
+}
+
+#[test]
+fn cycle_list() {
+    let head = build_cycle_list(vec![3, 2, 0, -4], 1);
+    let second = head.as_ref().unwrap().borrow().next.clone();
+    let mut tail = second.clone();
+    for _ in 0..2 {
+        tail = tail.unwrap().borrow().next.clone();
+    }
+    let back = tail.unwrap().borrow().next.clone();
+    assert!(same_node(&back, &second));
+    assert_eq!(shared_list_index(&head, &back), Some(1));
+
+    let head = build_cycle_list(vec![1], -1);
+    assert!(head.unwrap().borrow().next.is_none());
+}
+
+#[test]
+#[should_panic(expected = "skipA must not be negative")]
+fn intersection_lists_negative_skip() {
+    build_intersection_lists(8, vec![4, 1, 8], vec![5, 8], -1, 1);
+}
+
+#[test]
+fn intersection_lists() {
+    let (a, b, node) =
+        build_intersection_lists(8, vec![4, 1, 8, 4, 5], vec![5, 6, 1, 8, 4, 5], 2, 3);
+    assert_eq!(node.as_ref().unwrap().borrow().val, 8);
+    assert_eq!(shared_list_index(&a, &node), Some(2));
+    assert_eq!(shared_list_index(&b, &node), Some(3));
+
+    let (a, b, node) = build_intersection_lists(0, vec![2, 6, 4], vec![1, 5], 3, 2);
+    assert!(node.is_none());
+    assert_eq!(shared_list_index(&a, &b), None);
+}
@@ -0,0 +1,166 @@
+//! Memory allocated by the solution, counted by an opt-in global allocator.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A global allocator counting the bytes each thread allocates, so tests running in parallel
+/// are measured apart. Opt in from the project's `lib.rs`:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: CountingAlloc = CountingAlloc;
+/// ```
+pub struct CountingAlloc;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // freed memory may come from another thread, the current count can go below zero
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+    static TOTAL: Cell<usize> = const { Cell::new(0) };
+}
+
+fn on_alloc(size: usize) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    count_alloc(size);
+}
+
+fn count_alloc(size: usize) {
+    // the thread locals are gone while the thread exits
+    let _ = CURRENT.try_with(|current| {
+        let v = current.get() + size as isize;
+        current.set(v);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(v)));
+    });
+    let _ = TOTAL.try_with(|total| total.set(total.get() + size));
+}
+
+fn on_dealloc(size: usize) {
+    let _ = CURRENT.try_with(|current| current.set(current.get() - size as isize));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            on_dealloc(layout.size());
+            on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Bytes allocated while measuring: the most held at once, and the sum of every allocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub peak: usize,
+    pub total: usize,
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "peak {}, total {}",
+            format_bytes(self.peak),
+            format_bytes(self.total)
+        )
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    let b = bytes as f64;
+    if b < 1024.0 {
+        format!("{bytes} B")
+    } else if b < 1024.0 * 1024.0 {
+        format!("{:.1} KB", b / 1024.0)
+    } else {
+        format!("{:.1} MB", b / 1024.0 / 1024.0)
+    }
+}
+
+/// Run `f`, counting what the thread allocates meanwhile. `None` when `CountingAlloc` is not
+/// the global allocator.
+pub fn measure_memory<T>(f: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    let (value, usage) = count_memory(f);
+    (value, INSTALLED.load(Ordering::Relaxed).then_some(usage))
+}
+
+fn count_memory<T>(f: impl FnOnce() -> T) -> (T, MemoryUsage) {
+    let start = CURRENT.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(start));
+    let start_total = TOTAL.with(Cell::get);
+    let value = f();
+    let peak = PEAK.with(|peak| peak.replace(peak.get().max(outer_peak)));
+    let usage = MemoryUsage {
+        peak: (peak - start).max(0) as usize,
+        total: TOTAL.with(Cell::get) - start_total,
+    };
+    (value, usage)
+}
+
+/// Print the memory `label` used, failing above `LEETCODE_MEMORY_LIMIT_MB` (default 256).
+pub(crate) fn report_memory(label: &str, usage: MemoryUsage) {
+    let limit = std::env::var("LEETCODE_MEMORY_LIMIT_MB")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(256);
+    println!("{label}: {usage}");
+    assert!(
+        usage.peak <= limit * 1024 * 1024,
+        "{label}: peak {} over the memory limit of {limit} MB",
+        format_bytes(usage.peak)
+    );
+}
+
+#[test]
+fn memory_usage() {
+    // the test binary runs with the system allocator
+    let (len, usage) = measure_memory(|| vec![0u8; 1000].len());
+    assert_eq!(len, 1000);
+    assert_eq!(usage, None);
+
+    // the counts are per thread, other tests do not interfere
+    let (_, usage) = count_memory(|| {
+        count_alloc(4096);
+        on_dealloc(4096);
+        count_alloc(1024);
+        on_dealloc(1024);
+    });
+    assert_eq!(
+        usage,
+        MemoryUsage {
+            peak: 4096,
+            total: 5120
+        }
+    );
+    assert_eq!(usage.to_string(), "peak 4.0 KB, total 5.0 KB");
+}
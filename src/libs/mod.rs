@@ -1,3 +1,10 @@
+pub(crate) mod interactive;
+pub(crate) mod json;
 pub(crate) mod list;
+pub(crate) mod memory;
+pub(crate) mod nested;
+pub(crate) mod node;
+pub(crate) mod perf;
+pub(crate) mod stress;
 pub(crate) mod test;
 pub(crate) mod tree;
@@ -0,0 +1,68 @@
+use serde_json::Value;
+
+/// Nested list problems: `[[1,1],2,[1,1]]`, the same enum LeetCode's Rust snippets declare.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NestedInteger {
+    Int(i32),
+    List(Vec<NestedInteger>),
+}
+
+impl NestedInteger {
+    pub fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Array(items) => {
+                NestedInteger::List(items.iter().map(Self::from_value).collect())
+            }
+            v => NestedInteger::Int(
+                i32::try_from(v.as_i64().expect("expect integer or list"))
+                    .expect("integer out of i32 range"),
+            ),
+        }
+    }
+
+    pub fn from_jsonstr(s: &str) -> Self {
+        Self::from_value(&serde_json::from_str(s).expect("invalid json"))
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            NestedInteger::Int(v) => Value::from(*v),
+            NestedInteger::List(items) => Value::Array(items.iter().map(Self::to_value).collect()),
+        }
+    }
+}
+
+/// `[start, end]` of interval problems.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Interval {
+    pub start: i32,
+    pub end: i32,
+}
+
+impl Interval {
+    #[inline]
+    pub fn new(start: i32, end: i32) -> Self {
+        Interval { start, end }
+    }
+}
+
+#[test]
+fn nested_integer() {
+    let s = "[[1,1],2,[1,[4,[6]]]]";
+    let nested = NestedInteger::from_jsonstr(s);
+    assert_eq!(
+        nested,
+        NestedInteger::List(vec![
+            NestedInteger::List(vec![NestedInteger::Int(1), NestedInteger::Int(1)]),
+            NestedInteger::Int(2),
+            NestedInteger::List(vec![
+                NestedInteger::Int(1),
+                NestedInteger::List(vec![
+                    NestedInteger::Int(4),
+                    NestedInteger::List(vec![NestedInteger::Int(6)])
+                ])
+            ]),
+        ])
+    );
+    assert_eq!(nested.to_value().to_string(), s);
+}
@@ -0,0 +1,401 @@
+//! LeetCode's `Node` types. Each problem family defines its own `Node`, they are named
+//! after their shape here and read from / written to the JSON form of the problem.
+
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use serde_json::Value;
+
+fn parse_array(s: &str) -> Vec<Value> {
+    match serde_json::from_str::<Value>(s) {
+        Ok(Value::Array(array)) => array,
+        _ => vec![],
+    }
+}
+
+fn as_i32(value: &Value) -> Option<i32> {
+    value
+        .as_i64()
+        .map(|v| i32::try_from(v).expect("integer out of i32 range"))
+}
+
+fn trim_nulls(list: &mut Vec<Value>) {
+    while let Some(Value::Null) = list.last() {
+        let _ = list.pop();
+    }
+}
+
+/// Clone graph: `[[2,4],[1,3],[2,4],[1,3]]` lists the neighbors of the nodes valued 1, 2, ...
+#[derive(Debug)]
+pub struct GraphNode {
+    pub val: i32,
+    pub neighbors: Vec<Rc<RefCell<GraphNode>>>,
+}
+
+impl GraphNode {
+    #[inline]
+    pub fn new(val: i32) -> Self {
+        GraphNode {
+            val,
+            neighbors: vec![],
+        }
+    }
+
+    /// The node valued 1 of the adjacency list.
+    pub fn from_jsonstr(s: &str) -> Option<Rc<RefCell<GraphNode>>> {
+        let adjacency = parse_array(s);
+        let nodes = (1..=adjacency.len() as i32)
+            .map(|val| Rc::new(RefCell::new(GraphNode::new(val))))
+            .collect::<Vec<_>>();
+        for (node, neighbors) in nodes.iter().zip(adjacency.iter()) {
+            node.borrow_mut().neighbors = neighbors
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(as_i32)
+                .filter_map(|val| nodes.get(graph_index(val)?).cloned())
+                .collect();
+        }
+        nodes.first().cloned()
+    }
+
+    /// Adjacency list of every node reachable from `node`.
+    pub fn to_value(node: &Option<Rc<RefCell<GraphNode>>>) -> Value {
+        let mut adjacency: Vec<Option<Vec<i32>>> = vec![];
+        let mut queue = VecDeque::from_iter(node.iter().cloned());
+        while let Some(node) = queue.pop_front() {
+            let node = node.borrow();
+            let Some(index) = graph_index(node.val) else {
+                continue;
+            };
+            if adjacency.len() <= index {
+                adjacency.resize(index + 1, None);
+            }
+            if adjacency[index].is_some() {
+                continue;
+            }
+            adjacency[index] = Some(node.neighbors.iter().map(|n| n.borrow().val).collect());
+            queue.extend(node.neighbors.iter().cloned());
+        }
+        Value::from(
+            adjacency
+                .into_iter()
+                .map(Option::unwrap_or_default)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Index of the node valued `val` in the adjacency list, `None` unless `val >= 1`.
+fn graph_index(val: i32) -> Option<usize> {
+    usize::try_from(val).ok()?.checked_sub(1)
+}
+
+/// Copy list with random pointer: `[[7,null],[13,0]]` holds the value and the index of the
+/// node `random` points to.
+#[derive(Debug)]
+pub struct RandomNode {
+    pub val: i32,
+    pub next: Option<Rc<RefCell<RandomNode>>>,
+    pub random: Option<Rc<RefCell<RandomNode>>>,
+}
+
+impl RandomNode {
+    #[inline]
+    pub fn new(val: i32) -> Self {
+        RandomNode {
+            val,
+            next: None,
+            random: None,
+        }
+    }
+
+    pub fn from_jsonstr(s: &str) -> Option<Rc<RefCell<RandomNode>>> {
+        let items = parse_array(s);
+        let nodes = items
+            .iter()
+            .map(|item| {
+                let val = item.get(0).and_then(as_i32).unwrap_or_default();
+                Rc::new(RefCell::new(RandomNode::new(val)))
+            })
+            .collect::<Vec<_>>();
+        for (i, item) in items.iter().enumerate() {
+            let mut node = nodes[i].borrow_mut();
+            node.next = nodes.get(i + 1).cloned();
+            node.random = item
+                .get(1)
+                .and_then(Value::as_u64)
+                .and_then(|j| nodes.get(j as usize).cloned());
+        }
+        nodes.first().cloned()
+    }
+
+    pub fn to_value(head: &Option<Rc<RefCell<RandomNode>>>) -> Value {
+        let mut nodes = vec![];
+        let mut cur = head.clone();
+        while let Some(node) = cur {
+            cur = node.borrow().next.clone();
+            nodes.push(node);
+        }
+        let items = nodes
+            .iter()
+            .map(|node| {
+                let node = node.borrow();
+                let random = node
+                    .random
+                    .as_ref()
+                    .and_then(|r| nodes.iter().position(|n| Rc::ptr_eq(n, r)));
+                Value::from(vec![Value::from(node.val), Value::from(random)])
+            })
+            .collect::<Vec<_>>();
+        Value::Array(items)
+    }
+}
+
+/// N-ary tree in level order, each group of children ended by `null`: `[1,null,3,2,4,null,5,6]`.
+#[derive(Debug)]
+pub struct NaryNode {
+    pub val: i32,
+    pub children: Vec<Rc<RefCell<NaryNode>>>,
+}
+
+impl NaryNode {
+    #[inline]
+    pub fn new(val: i32) -> Self {
+        NaryNode {
+            val,
+            children: vec![],
+        }
+    }
+
+    pub fn from_jsonstr(s: &str) -> Option<Rc<RefCell<NaryNode>>> {
+        let items = parse_array(s);
+        let mut iter = items.iter();
+        let root = Rc::new(RefCell::new(NaryNode::new(iter.next().and_then(as_i32)?)));
+        let _ = iter.next();
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(parent) = queue.pop_front() {
+            for item in iter.by_ref() {
+                match as_i32(item) {
+                    Some(val) => {
+                        let child = Rc::new(RefCell::new(NaryNode::new(val)));
+                        parent.borrow_mut().children.push(child.clone());
+                        queue.push_back(child);
+                    }
+                    None => break,
+                }
+            }
+        }
+        Some(root)
+    }
+
+    pub fn to_value(root: &Option<Rc<RefCell<NaryNode>>>) -> Value {
+        let mut list = vec![];
+        if let Some(root) = root {
+            list.push(Value::from(root.borrow().val));
+            list.push(Value::Null);
+            let mut queue = VecDeque::from([root.clone()]);
+            while let Some(node) = queue.pop_front() {
+                for child in node.borrow().children.iter() {
+                    list.push(Value::from(child.borrow().val));
+                    queue.push_back(child.clone());
+                }
+                list.push(Value::Null);
+            }
+        }
+        trim_nulls(&mut list);
+        Value::Array(list)
+    }
+}
+
+/// Populating next right pointers: built from a level order tree like `TreeNode`, written
+/// level by level along `next` with `"#"` ending each level: `[1,"#",2,3,"#"]`.
+#[derive(Debug)]
+pub struct NextNode {
+    pub val: i32,
+    pub left: Option<Rc<RefCell<NextNode>>>,
+    pub right: Option<Rc<RefCell<NextNode>>>,
+    pub next: Option<Rc<RefCell<NextNode>>>,
+}
+
+impl NextNode {
+    #[inline]
+    pub fn new(val: i32) -> Self {
+        NextNode {
+            val,
+            left: None,
+            right: None,
+            next: None,
+        }
+    }
+
+    pub fn from_jsonstr(s: &str) -> Option<Rc<RefCell<NextNode>>> {
+        let items = parse_array(s);
+        let new_node = |item: Option<&Value>| {
+            item.and_then(as_i32)
+                .map(|val| Rc::new(RefCell::new(NextNode::new(val))))
+        };
+        let mut iter = items.iter();
+        let root = new_node(iter.next())?;
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(node) = queue.pop_front() {
+            let left = new_node(iter.next());
+            let right = new_node(iter.next());
+            queue.extend(left.iter().chain(right.iter()).cloned());
+            let mut node = node.borrow_mut();
+            node.left = left;
+            node.right = right;
+        }
+        Some(root)
+    }
+
+    pub fn to_value(root: &Option<Rc<RefCell<NextNode>>>) -> Value {
+        let mut list = vec![];
+        let mut level = root.iter().cloned().collect::<Vec<_>>();
+        while let Some(first) = level.first().cloned() {
+            let mut cur = Some(first);
+            while let Some(node) = cur {
+                list.push(Value::from(node.borrow().val));
+                cur = node.borrow().next.clone();
+            }
+            list.push(Value::from("#"));
+            level = level
+                .iter()
+                .flat_map(|node| {
+                    let node = node.borrow();
+                    [node.left.clone(), node.right.clone()]
+                })
+                .flatten()
+                .collect();
+        }
+        Value::Array(list)
+    }
+}
+
+/// Quad tree in level order of `[isLeaf, val]`, every node followed by its four children
+/// slots: `[[0,1],[1,0],[1,1],[1,1],[1,0]]`.
+#[derive(Debug)]
+pub struct QuadNode {
+    pub val: bool,
+    pub is_leaf: bool,
+    pub top_left: Option<Rc<RefCell<QuadNode>>>,
+    pub top_right: Option<Rc<RefCell<QuadNode>>>,
+    pub bottom_left: Option<Rc<RefCell<QuadNode>>>,
+    pub bottom_right: Option<Rc<RefCell<QuadNode>>>,
+}
+
+impl QuadNode {
+    #[inline]
+    pub fn new(val: bool, is_leaf: bool) -> Self {
+        QuadNode {
+            val,
+            is_leaf,
+            top_left: None,
+            top_right: None,
+            bottom_left: None,
+            bottom_right: None,
+        }
+    }
+
+    pub fn from_jsonstr(s: &str) -> Option<Rc<RefCell<QuadNode>>> {
+        let items = parse_array(s);
+        let new_node = |item: Option<&Value>| {
+            let pair = item?.as_array()?;
+            let flag = |i: usize| pair.get(i).and_then(Value::as_i64).unwrap_or_default() != 0;
+            Some(Rc::new(RefCell::new(QuadNode::new(flag(1), flag(0)))))
+        };
+        let mut iter = items.iter();
+        let root = new_node(iter.next())?;
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(node) = queue.pop_front() {
+            let children = [(); 4].map(|_| new_node(iter.next()));
+            queue.extend(children.iter().flatten().cloned());
+            let [top_left, top_right, bottom_left, bottom_right] = children;
+            let mut node = node.borrow_mut();
+            node.top_left = top_left;
+            node.top_right = top_right;
+            node.bottom_left = bottom_left;
+            node.bottom_right = bottom_right;
+        }
+        Some(root)
+    }
+
+    pub fn to_value(root: &Option<Rc<RefCell<QuadNode>>>) -> Value {
+        let mut list = vec![];
+        let mut queue = VecDeque::from([root.clone()]);
+        while let Some(node) = queue.pop_front() {
+            match node {
+                Some(node) => {
+                    let node = node.borrow();
+                    list.push(Value::from(vec![node.is_leaf as i32, node.val as i32]));
+                    queue.extend([
+                        node.top_left.clone(),
+                        node.top_right.clone(),
+                        node.bottom_left.clone(),
+                        node.bottom_right.clone(),
+                    ]);
+                }
+                None => list.push(Value::Null),
+            }
+        }
+        trim_nulls(&mut list);
+        Value::Array(list)
+    }
+}
+
+#[test]
+fn graph() {
+    let s = "[[2,4],[1,3],[2,4],[1,3]]";
+    let node = GraphNode::from_jsonstr(s);
+    assert_eq!(node.as_ref().unwrap().borrow().neighbors.len(), 2);
+    assert_eq!(GraphNode::to_value(&node).to_string(), s);
+    assert_eq!(
+        GraphNode::to_value(&GraphNode::from_jsonstr("[]")),
+        serde_json::json!([])
+    );
+    // neighbors outside 1..=n are skipped rather than underflowing
+    let node = GraphNode::from_jsonstr("[[0,2,-1,3],[1]]");
+    assert_eq!(GraphNode::to_value(&node), serde_json::json!([[2], [1]]));
+    let node = Some(Rc::new(RefCell::new(GraphNode::new(0))));
+    assert_eq!(GraphNode::to_value(&node), serde_json::json!([]));
+}
+
+#[test]
+fn random_list() {
+    let s = "[[7,null],[13,0],[11,4],[10,2],[1,0]]";
+    let head = RandomNode::from_jsonstr(s);
+    assert_eq!(RandomNode::to_value(&head).to_string(), s);
+}
+
+#[test]
+fn nary_tree() {
+    let s =
+        "[1,null,2,3,4,5,null,null,6,7,null,8,null,9,10,null,null,11,null,12,null,13,null,null,14]";
+    let root = NaryNode::from_jsonstr(s);
+    assert_eq!(root.as_ref().unwrap().borrow().children.len(), 4);
+    assert_eq!(NaryNode::to_value(&root).to_string(), s);
+}
+
+#[test]
+fn next_tree() {
+    let root = NextNode::from_jsonstr("[1,2,3,4,5,null,7]");
+    {
+        let root = root.as_ref().unwrap().borrow();
+        let (left, right) = (root.left.clone().unwrap(), root.right.clone().unwrap());
+        left.borrow_mut().next = Some(right.clone());
+        let (four, five) = (left.borrow().left.clone(), left.borrow().right.clone());
+        four.unwrap().borrow_mut().next = five.clone();
+        five.unwrap().borrow_mut().next = right.borrow().right.clone();
+    }
+    assert_eq!(
+        NextNode::to_value(&root),
+        serde_json::json!([1, "#", 2, 3, "#", 4, 5, 7, "#"])
+    );
+}
+
+#[test]
+fn quad_tree() {
+    let s = "[[0,1],[1,1],[0,1],[1,1],[1,0],null,null,null,null,[1,0],[1,0],[1,1],[1,1]]";
+    let root = QuadNode::from_jsonstr(s);
+    assert!(!root.as_ref().unwrap().borrow().is_leaf);
+    assert_eq!(QuadNode::to_value(&root).to_string(), s);
+}
@@ -0,0 +1,458 @@
+//! Worst-case timing: the solution run on the largest inputs the constraints allow, and at
+//! growing sizes to estimate its complexity.
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::bail;
+use serde_json::Value;
+
+use super::{
+    stress::InputGen,
+    test::{format_table, load_testcases},
+};
+
+/// Complexity classes the timings are fit to, slowest growing first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Complexity {
+    Constant,
+    Log,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 7] = [
+        Complexity::Constant,
+        Complexity::Log,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+        Complexity::Exponential,
+    ];
+
+    /// `ln f(n)` of the class.
+    fn ln_growth(&self, n: f64) -> f64 {
+        let n = n.max(2.0);
+        match self {
+            Complexity::Constant => 0.0,
+            Complexity::Log => n.ln().ln(),
+            Complexity::Linear => n.ln(),
+            Complexity::Linearithmic => n.ln() + n.ln().ln(),
+            Complexity::Quadratic => 2.0 * n.ln(),
+            Complexity::Cubic => 3.0 * n.ln(),
+            Complexity::Exponential => n * std::f64::consts::LN_2,
+        }
+    }
+}
+
+impl FromStr for Complexity {
+    type Err = anyhow::Error;
+
+    /// `O(n log n)`, `O(n^2)`, `O(n²)`, ... in any spacing.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let compact = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '*' && *c != '·')
+            .collect::<String>()
+            .to_lowercase();
+        let inner = compact
+            .strip_prefix("o(")
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(&compact);
+        let class = match inner {
+            "1" => Complexity::Constant,
+            "logn" | "log(n)" => Complexity::Log,
+            "n" => Complexity::Linear,
+            "nlogn" | "nlog(n)" => Complexity::Linearithmic,
+            "n^2" | "n²" | "nn" => Complexity::Quadratic,
+            "n^3" | "n³" | "nnn" => Complexity::Cubic,
+            "2^n" | "2ⁿ" => Complexity::Exponential,
+            _ => bail!("unknown complexity `{s}`"),
+        };
+        Ok(class)
+    }
+}
+
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Log => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+            Complexity::Exponential => "O(2ⁿ)",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The class fitting the `(n, seconds)` samples best: the one whose `f(n)` is off the times by
+/// the steadiest factor, by least squares of the logarithms. `None` below three sizes.
+pub fn estimate_complexity(samples: &[(f64, f64)]) -> Option<Complexity> {
+    if samples.len() < 3 {
+        return None;
+    }
+    let residual = |class: &Complexity| {
+        let logs = samples
+            .iter()
+            .map(|(n, t)| t.max(1e-9).ln() - class.ln_growth(*n))
+            .collect::<Vec<_>>();
+        let mean = logs.iter().sum::<f64>() / logs.len() as f64;
+        logs.iter().map(|l| (l - mean).powi(2)).sum::<f64>()
+    };
+    Complexity::ALL
+        .into_iter()
+        .min_by(|a, b| residual(a).total_cmp(&residual(b)))
+}
+
+/// The complexity declared by a `/// complexity: O(n)` line of the solution file.
+fn declared_complexity(source: &str) -> Option<&str> {
+    source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("/// complexity:"))
+        .map(str::trim)
+}
+
+/// The best of repeated runs, the arguments converted outside of the timing.
+fn time<F: FnOnce() -> Value>(prepare: &impl Fn(&[Value]) -> F, inputs: &[Value]) -> Duration {
+    let (mut best, mut total) = (Duration::MAX, Duration::ZERO);
+    for _ in 0..100 {
+        let call = prepare(inputs);
+        let start = Instant::now();
+        std::hint::black_box(call());
+        let elapsed = start.elapsed();
+        best = best.min(elapsed);
+        total += elapsed;
+        if total > Duration::from_millis(20) {
+            break;
+        }
+    }
+    best
+}
+
+/// The size of the inputs: the longest list or string, or the largest integer without any.
+fn input_size(inputs: &[Value]) -> f64 {
+    fn elements(value: &Value) -> Option<usize> {
+        match value {
+            Value::Array(items) => Some(
+                items
+                    .iter()
+                    .map(|v| elements(v).unwrap_or(usize::from(!v.is_null())))
+                    .sum(),
+            ),
+            Value::String(s) => Some(s.chars().count()),
+            _ => None,
+        }
+    }
+    match inputs.iter().filter_map(elements).max() {
+        Some(n) => n as f64,
+        None => inputs.iter().filter_map(Value::as_f64).fold(0.0, f64::max),
+    }
+}
+
+/// Run the solution on maximal inputs of `params` within `constraints` and report the wall time
+/// of each, failing when one takes longer than `LEETCODE_PERF_BUDGET_MS` (default 1000). Then
+/// estimate its complexity from inputs of doubling sizes, warning when it grows faster than
+/// the `/// complexity:` line of `source` declares.
+///
+/// `prepare` converts the inputs to arguments and returns the call, only the call is timed.
+/// Inputs are the same from one run to the next, build in release mode for meaningful times.
+pub fn perf<F: FnOnce() -> Value>(
+    params: &[(&str, &str)],
+    constraints: &[&str],
+    source: &str,
+    prepare: impl Fn(&[Value]) -> F,
+) {
+    let budget = std::env::var("LEETCODE_PERF_BUDGET_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map_or(Duration::from_secs(1), Duration::from_millis);
+    perf_with_budget(params, constraints, source, prepare, budget)
+}
+
+fn perf_with_budget<F: FnOnce() -> Value>(
+    params: &[(&str, &str)],
+    constraints: &[&str],
+    source: &str,
+    prepare: impl Fn(&[Value]) -> F,
+    budget: Duration,
+) {
+    let names = params.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let mut gen = InputGen::new(params, constraints, 0);
+
+    let mut rows = vec![];
+    let mut slow = vec![];
+    for (shape, inputs) in gen.maximal() {
+        let elapsed = time(&prepare, &inputs);
+        let over = elapsed > budget;
+        if over {
+            slow.push(shape.to_string());
+        }
+        rows.push(vec![
+            shape.to_string(),
+            describe(&names, &inputs),
+            format_duration(elapsed),
+            if over { "✗" } else { "✓" }.to_owned(),
+        ]);
+    }
+    println!("\n{}", format_table(&rows));
+
+    // from 1/1024 of the largest inputs up, until one takes longer than the budget
+    let mut samples: Vec<(f64, f64)> = vec![];
+    for k in (0..=10).rev() {
+        let inputs = gen.scaled(1.0 / (1 << k) as f64);
+        let n = input_size(&inputs);
+        if samples.last().is_some_and(|(last, _)| *last >= n) {
+            continue;
+        }
+        let elapsed = time(&prepare, &inputs);
+        samples.push((n, elapsed.as_secs_f64()));
+        if elapsed > budget {
+            break;
+        }
+    }
+    let rows = samples
+        .iter()
+        .map(|(n, t)| {
+            vec![
+                format!("n = {n}"),
+                format_duration(Duration::from_secs_f64(*t)),
+            ]
+        })
+        .collect::<Vec<_>>();
+    println!("\n{}", format_table(&rows));
+    let estimated = estimate_complexity(&samples);
+    let declared = declared_complexity(source);
+    println!(
+        "\nestimated {}, declared {}",
+        estimated.map_or("?".to_owned(), |c| c.to_string()),
+        declared.unwrap_or("? (add a `/// complexity: O(..)` line)")
+    );
+
+    assert!(
+        slow.is_empty(),
+        "over the budget of {} ms: {}",
+        budget.as_millis(),
+        slow.join(", ")
+    );
+    if let Some(warning) = complexity_warning(estimated, declared) {
+        println!("warning: {warning}");
+    }
+}
+
+/// Why the estimate does not match the declared complexity, if it grows faster or the
+/// declaration can not be read. Timings are noisy, so this is only printed.
+fn complexity_warning(estimated: Option<Complexity>, declared: Option<&str>) -> Option<String> {
+    let (estimated, declared) = (estimated?, declared?);
+    match declared.parse::<Complexity>() {
+        Ok(class) if estimated > class => Some(format!(
+            "estimated {estimated} grows faster than the declared {class}"
+        )),
+        Ok(_) => None,
+        Err(err) => Some(err.to_string()),
+    }
+}
+
+/// Time every variant `(name, prepare)` on the cases of the testcases file at `path` and on
+/// maximal inputs, in a table with a column per variant, the fastest marked with `*`.
+pub fn bench<F: FnOnce() -> Value, P: Fn(&[Value]) -> F>(
+    path: &str,
+    params: &[(&str, &str)],
+    constraints: &[&str],
+    variants: &[(&str, P)],
+) {
+    let names = params.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let mut inputs = load_testcases(path, params.len())
+        .into_iter()
+        .map(|case| (format!("case {}", case.index), case.inputs))
+        .collect::<Vec<_>>();
+    let mut gen = InputGen::new(params, constraints, 0);
+    inputs.extend(
+        gen.maximal()
+            .into_iter()
+            .map(|(shape, inputs)| (format!("{shape} ({})", describe(&names, &inputs)), inputs)),
+    );
+
+    let header = std::iter::once("input")
+        .chain(variants.iter().map(|(name, _)| *name))
+        .map(str::to_owned)
+        .collect();
+    let mut rows = vec![header];
+    for (label, inputs) in &inputs {
+        let times = variants
+            .iter()
+            .map(|(_, prepare)| time(prepare, inputs))
+            .collect::<Vec<_>>();
+        let fastest = times.iter().min().copied();
+        let mut row = vec![label.clone()];
+        row.extend(times.iter().map(|t| {
+            let mark = if variants.len() > 1 && Some(*t) == fastest {
+                " *"
+            } else {
+                ""
+            };
+            format!("{}{mark}", format_duration(*t))
+        }));
+        rows.push(row);
+    }
+    println!("\n{}", format_table(&rows));
+}
+
+fn format_duration(d: Duration) -> String {
+    if d < Duration::from_millis(1) {
+        format!("{:.1} µs", d.as_secs_f64() * 1e6)
+    } else {
+        format!("{:.1} ms", d.as_secs_f64() * 1e3)
+    }
+}
+
+/// Sizes of lists and strings, values of scalars: `nums: 100000, k: 3`.
+fn describe(names: &[&str], inputs: &[Value]) -> String {
+    names
+        .iter()
+        .zip(inputs)
+        .map(|(name, value)| format!("{name}: {}", size(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn size(value: &Value) -> String {
+    match value {
+        Value::Array(items) => match items.first() {
+            Some(first @ Value::Array(_)) => format!("{}x{}", items.len(), size(first)),
+            // nodes of a tree, without the nulls of its level order
+            _ => items.iter().filter(|v| !v.is_null()).count().to_string(),
+        },
+        Value::String(s) => s.chars().count().to_string(),
+        v => v.to_string(),
+    }
+}
+
+#[test]
+fn maximal_inputs() {
+    let mut gen = InputGen::new(
+        &[("nums", "integer[]"), ("root", "TreeNode")],
+        &["1 <= nums.length <= 10^5", "-10^9 <= nums[i] <= 10^9"],
+        0,
+    );
+    let cases = gen.maximal();
+    let shapes = cases
+        .iter()
+        .map(|(shape, _)| shape.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(shapes, ["random", "sorted", "reversed", "all equal"]);
+    for (_, inputs) in &cases {
+        assert_eq!(size(&inputs[0]), "100000");
+        assert!(inputs[0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|v| v.as_i64().unwrap().abs() <= 1_000_000_000));
+    }
+    let sorted = &cases[1].1;
+    assert!(sorted[0]
+        .as_array()
+        .unwrap()
+        .windows(2)
+        .all(|w| w[0].as_i64() <= w[1].as_i64()));
+    // 10^4 nodes without a bound, in a chain to the right
+    let chain = sorted[1].as_array().unwrap();
+    assert_eq!(chain.len(), 19999);
+    assert!(chain.iter().skip(1).step_by(2).all(Value::is_null));
+}
+
+#[test]
+fn perf_budget() {
+    let result = std::panic::catch_unwind(|| {
+        perf_with_budget(
+            &[("n", "integer")],
+            &["1 <= n <= 10"],
+            "",
+            |_| {
+                || {
+                    std::thread::sleep(Duration::from_millis(5));
+                    Value::Null
+                }
+            },
+            Duration::from_millis(1),
+        )
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn complexity() {
+    let linear = (1..8)
+        .map(|k| (1000.0 * k as f64, 2e-6 * k as f64))
+        .collect::<Vec<_>>();
+    assert_eq!(estimate_complexity(&linear), Some(Complexity::Linear));
+    let quadratic = (1..8)
+        .map(|k| (1000.0 * k as f64, 3e-9 * (1000.0 * k as f64).powi(2)))
+        .collect::<Vec<_>>();
+    assert_eq!(estimate_complexity(&quadratic), Some(Complexity::Quadratic));
+    assert_eq!(estimate_complexity(&linear[..2]), None);
+
+    assert_eq!(
+        "O(n log n)".parse::<Complexity>().unwrap(),
+        Complexity::Linearithmic
+    );
+    assert_eq!(
+        "O(n^2)".parse::<Complexity>().unwrap(),
+        Complexity::Quadratic
+    );
+    assert!("O(m + n)".parse::<Complexity>().is_err());
+    assert_eq!(
+        declared_complexity("/// # Two Sum\n    /// complexity: O(n)\n"),
+        Some("O(n)")
+    );
+}
+
+#[test]
+fn perf_declared() {
+    let quadratic = (1..8)
+        .map(|k| {
+            let n = 250.0 * k as f64;
+            // 1.5 * n^2 ns, a few percent off
+            (n, 1.5e-9 * n * n * (1.0 + 0.03 * (k % 3) as f64))
+        })
+        .collect::<Vec<_>>();
+    let estimated = estimate_complexity(&quadratic);
+    assert_eq!(estimated, Some(Complexity::Quadratic));
+    assert_eq!(
+        complexity_warning(estimated, Some("O(n)")).as_deref(),
+        Some("estimated O(n²) grows faster than the declared O(n)")
+    );
+    assert_eq!(complexity_warning(estimated, Some("O(n^2)")), None);
+    assert_eq!(complexity_warning(estimated, Some("O(n^3)")), None);
+    assert_eq!(
+        complexity_warning(estimated, Some("O(m + n)")).as_deref(),
+        Some("unknown complexity `O(m + n)`")
+    );
+    assert_eq!(complexity_warning(estimated, None), None);
+}
+
+#[test]
+fn bench_variants() {
+    let path = std::env::temp_dir().join("leetcode-tool-bench.txt");
+    std::fs::write(&path, "[1,2,3]\n6\n").unwrap();
+    let sum = |inputs: &[Value]| {
+        let nums: Vec<i64> = serde_json::from_value(inputs[0].clone()).unwrap();
+        move || Value::from(nums.iter().sum::<i64>())
+    };
+    bench(
+        path.to_str().unwrap(),
+        &[("nums", "integer[]")],
+        &["1 <= nums.length <= 100"],
+        &[("iter", sum), ("again", sum)],
+    );
+}
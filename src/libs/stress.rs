@@ -0,0 +1,716 @@
+//! Differential testing: random inputs within the problem's constraints, the solution checked
+//! against a brute force, the failing input shrunk before it is reported.
+
+use std::{
+    collections::{HashMap, HashSet},
+    panic::{self, AssertUnwindSafe},
+    sync::Mutex,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde_json::Value;
+
+use crate::{
+    constraint::{parse_constraint_lines, Bound, Constraints, Range, Term},
+    meta::{parse_type, MetaDataType},
+};
+
+/// Random inputs of the parameters `(name, type)` of a problem, `type` in LeetCode's type language.
+pub struct InputGen {
+    params: Vec<(String, MetaDataType)>,
+    constraints: HashMap<String, Constraints>,
+    rng: StdRng,
+}
+
+impl InputGen {
+    pub fn new(params: &[(&str, &str)], constraints: &[&str], seed: u64) -> Self {
+        let names = params.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        InputGen {
+            params: params
+                .iter()
+                .map(|(name, ty)| (name.to_string(), parse_type(ty)))
+                .collect(),
+            constraints: parse_constraint_lines(constraints, &names),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// One input per parameter, lengths and values kept within `size` where the constraints allow.
+    pub fn generate(&mut self, size: i64) -> Vec<Value> {
+        let mut inputs: Vec<Value> = vec![];
+        for i in 0..self.params.len() {
+            let (name, ty) = self.params[i].clone();
+            let c = self.constraints.get(&name).cloned().unwrap_or_default();
+            let ctx = Context {
+                params: &self.params,
+                inputs: &inputs,
+            };
+            // nested lists are rectangular, `n == grid[i].length`
+            let lengths = (0..list_depth(&ty))
+                .map(|depth| {
+                    let range = ctx.range(c.length(depth), (0, size));
+                    pick(&mut self.rng, range, size) as usize
+                })
+                .collect::<Vec<_>>();
+            let mut g = Gen {
+                rng: &mut self.rng,
+                c: &c,
+                ctx,
+                lengths,
+                size,
+                scale: None,
+            };
+            let value = g.value(&ty, 0);
+            inputs.push(value);
+        }
+        inputs
+    }
+
+    /// The largest inputs the constraints allow, with values spread over their whole range,
+    /// followed by adversarial variants: sorted, reversed and all-equal lists and strings, and
+    /// trees degenerated into a chain. Variants breaking the constraints are left out.
+    pub fn maximal(&mut self) -> Vec<(Shape, Vec<Value>)> {
+        let inputs = self.scaled(1.0);
+        let mut cases = vec![(Shape::Random, inputs.clone())];
+        for shape in [Shape::Sorted, Shape::Reversed, Shape::AllEqual] {
+            let reshaped = self
+                .params
+                .iter()
+                .zip(&inputs)
+                .map(|((_, ty), value)| reshape(value, ty, shape))
+                .collect::<Vec<_>>();
+            if reshaped != inputs && self.is_valid(&reshaped) {
+                cases.push((shape, reshaped));
+            }
+        }
+        cases
+    }
+
+    /// Inputs with lengths, and integers not inside a list, at `scale` of the largest the
+    /// constraints allow, values of lists spread over their whole range.
+    pub fn scaled(&mut self, scale: f64) -> Vec<Value> {
+        let mut inputs: Vec<Value> = vec![];
+        for i in 0..self.params.len() {
+            let (name, ty) = self.params[i].clone();
+            let c = self.constraints.get(&name).cloned().unwrap_or_default();
+            let ctx = Context {
+                params: &self.params,
+                inputs: &inputs,
+            };
+            // nested lists share the element budget
+            let mut budget = MAX_ELEMENTS;
+            let lengths = (0..list_depth(&ty))
+                .map(|depth| {
+                    let (min, max) = ctx.range(c.length(depth), (0, MAX_LENGTH));
+                    let len = scale_range((min, max), scale).clamp(0, budget);
+                    budget /= len.max(1);
+                    len as usize
+                })
+                .collect::<Vec<_>>();
+            let mut g = Gen {
+                rng: &mut self.rng,
+                c: &c,
+                ctx,
+                lengths,
+                size: i64::MAX,
+                scale: Some(scale),
+            };
+            let value = g.value(&ty, 0);
+            inputs.push(value);
+        }
+        inputs
+    }
+
+    /// Whether the inputs are still within the numeric bounds, used while shrinking.
+    fn is_valid(&self, inputs: &[Value]) -> bool {
+        let ctx = Context {
+            params: &self.params,
+            inputs,
+        };
+        self.params.iter().zip(inputs).all(|((name, ty), value)| {
+            let c = match self.constraints.get(name) {
+                Some(c) => c,
+                None => return true,
+            };
+            let len_ok = match (value, c.length(0)) {
+                (Value::Array(a), Some(range)) => ctx.contains(range, a.len() as i64),
+                (Value::String(s), Some(range)) => ctx.contains(range, s.chars().count() as i64),
+                _ => true,
+            };
+            let values_ok = match ty {
+                MetaDataType::Integer | MetaDataType::Long => match value.as_i64() {
+                    Some(v) => ctx.contains(&c.values, v),
+                    None => true,
+                },
+                MetaDataType::List(_) => leaves(value)
+                    .iter()
+                    .filter_map(|v| v.as_i64())
+                    .all(|v| ctx.contains(&c.values, v)),
+                _ => true,
+            };
+            let order_ok = match value {
+                Value::Array(a) => {
+                    (!c.unique || is_unique(a))
+                        && (!c.sorted || a.windows(2).all(|w| cmp_values(&w[0], &w[1]).is_le()))
+                }
+                _ => true,
+            };
+            len_ok && values_ok && order_ok
+        })
+    }
+}
+
+/// Length of lists, strings, linked lists and trees without an upper bound in the statement.
+const MAX_LENGTH: i64 = 10_000;
+/// Elements of a maximal input across all levels of nesting, `10^4 x 10^4` grids are cut down.
+const MAX_ELEMENTS: i64 = 1_000_000;
+
+/// The arrangement of a maximal input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Random,
+    Sorted,
+    Reversed,
+    AllEqual,
+}
+
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Shape::Random => "random",
+            Shape::Sorted => "sorted",
+            Shape::Reversed => "reversed",
+            Shape::AllEqual => "all equal",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The same elements arranged as `shape`, rows of a grid one by one.
+fn reshape(value: &Value, ty: &MetaDataType, shape: Shape) -> Value {
+    let arrange = |mut items: Vec<Value>| {
+        match shape {
+            Shape::Random => {}
+            Shape::Sorted => items.sort_by(cmp_values),
+            Shape::Reversed => items.sort_by(|a, b| cmp_values(b, a)),
+            Shape::AllEqual => {
+                if let Some(first) = items.first().cloned() {
+                    items.iter_mut().for_each(|item| *item = first.clone());
+                }
+            }
+        }
+        items
+    };
+    match (ty, value) {
+        (MetaDataType::TreeNode, Value::Array(items)) => {
+            let values = items.iter().filter(|v| !v.is_null()).cloned().collect();
+            match shape {
+                // a chain to the right, to the left for reversed
+                Shape::Sorted | Shape::Reversed => {
+                    let mut chain = vec![];
+                    for (i, v) in arrange(values).into_iter().enumerate() {
+                        if i > 0 && shape == Shape::Sorted {
+                            chain.push(Value::Null);
+                        }
+                        chain.push(v);
+                        if shape == Shape::Reversed {
+                            chain.push(Value::Null);
+                        }
+                    }
+                    while chain.last() == Some(&Value::Null) {
+                        chain.pop();
+                    }
+                    Value::Array(chain)
+                }
+                _ => {
+                    let mut values = arrange(values).into_iter();
+                    Value::Array(
+                        items
+                            .iter()
+                            .map(|v| match v {
+                                Value::Null => Value::Null,
+                                _ => values.next().unwrap_or_default(),
+                            })
+                            .collect(),
+                    )
+                }
+            }
+        }
+        (MetaDataType::List(t), Value::Array(items)) if list_depth(t) > 0 => {
+            Value::Array(items.iter().map(|v| reshape(v, t, shape)).collect())
+        }
+        (_, Value::Array(items)) => Value::Array(arrange(items.clone())),
+        (_, Value::String(s)) => {
+            let chars = s.chars().map(|c| Value::from(c.to_string())).collect();
+            Value::from(
+                arrange(chars)
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<String>(),
+            )
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Resolves bounds referring to the inputs generated so far.
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    params: &'a [(String, MetaDataType)],
+    inputs: &'a [Value],
+}
+
+impl Context<'_> {
+    fn bound(&self, bound: &Bound) -> Option<i64> {
+        let (term, offset) = match bound {
+            Bound::Int(v) => return Some(*v),
+            Bound::Ref(term, offset) => (term, offset),
+        };
+        let i = self
+            .params
+            .iter()
+            .position(|(name, _)| name == term.name())?;
+        let value = self.inputs.get(i)?;
+        let v = match (term, value) {
+            (Term::Length(_, 0), Value::Array(a)) => a.len() as i64,
+            (Term::Length(_, 0), Value::String(s)) => s.chars().count() as i64,
+            (Term::Value(_, 0), v) => v.as_i64()?,
+            _ => return None,
+        };
+        Some(v + offset)
+    }
+
+    fn range(&self, range: Option<&Range>, default: (i64, i64)) -> (i64, i64) {
+        let range = match range {
+            Some(range) => range,
+            None => return default,
+        };
+        let min = range.min.iter().filter_map(|b| self.bound(b)).max();
+        let max = range.max.iter().filter_map(|b| self.bound(b)).min();
+        let min = min.unwrap_or(default.0);
+        (min, max.unwrap_or(default.1).max(min))
+    }
+
+    fn contains(&self, range: &Range, v: i64) -> bool {
+        range
+            .min
+            .iter()
+            .filter_map(|b| self.bound(b))
+            .all(|min| v >= min)
+            && range
+                .max
+                .iter()
+                .filter_map(|b| self.bound(b))
+                .all(|max| v <= max)
+    }
+}
+
+/// A value in `[lo, hi]`, within `size` of zero or of the nearest end.
+fn pick(rng: &mut StdRng, (lo, hi): (i64, i64), size: i64) -> i64 {
+    let (lo, hi) = if hi < -size {
+        (lo.max(hi - size), hi)
+    } else if lo > size {
+        (lo, hi.min(lo + size))
+    } else {
+        (lo.max(-size), hi.min(size))
+    };
+    rng.gen_range(lo, hi + 1)
+}
+
+/// The point at `scale` of the way from `lo` to `hi`.
+fn scale_range((lo, hi): (i64, i64), scale: f64) -> i64 {
+    if scale >= 1.0 {
+        return hi;
+    }
+    lo.saturating_add((hi.saturating_sub(lo) as f64 * scale) as i64)
+}
+
+/// Levels of list nesting, the innermost string counting as one.
+fn list_depth(ty: &MetaDataType) -> usize {
+    match ty {
+        MetaDataType::List(t) => 1 + list_depth(t),
+        MetaDataType::String | MetaDataType::ListNode | MetaDataType::TreeNode => 1,
+        _ => 0,
+    }
+}
+
+struct Gen<'a> {
+    rng: &'a mut StdRng,
+    c: &'a Constraints,
+    ctx: Context<'a>,
+    lengths: Vec<usize>,
+    size: i64,
+    /// Lengths at this fraction of the longest, values from the whole range.
+    scale: Option<f64>,
+}
+
+impl Gen<'_> {
+    fn int(&mut self, default: (i64, i64)) -> i64 {
+        let (lo, hi) = self.ctx.range(Some(&self.c.values), default);
+        if self.scale.is_some() {
+            self.rng.gen_range(lo, hi.saturating_add(1))
+        } else {
+            pick(self.rng, (lo, hi), self.size)
+        }
+    }
+
+    /// Integers not inside a list take the upper bound when scaled: `n` is the input size.
+    fn scalar(&mut self, default: (i64, i64), depth: usize) -> i64 {
+        match self.scale {
+            Some(scale) if depth == 0 => {
+                scale_range(self.ctx.range(Some(&self.c.values), default), scale)
+            }
+            _ => self.int(default),
+        }
+    }
+
+    fn char(&mut self) -> char {
+        let alphabet = match &self.c.alphabet {
+            Some(alphabet) => alphabet.chars().collect::<Vec<_>>(),
+            None => ('a'..='z').collect(),
+        };
+        alphabet[self.rng.gen_range(0, alphabet.len())]
+    }
+
+    /// Lengths of strings, linked lists and trees vary from one to the next.
+    fn len(&mut self, depth: usize) -> usize {
+        if self.scale.is_some() {
+            return self.lengths.get(depth).copied().unwrap_or_default();
+        }
+        let range = self.ctx.range(self.c.length(depth), (0, self.size));
+        pick(self.rng, range, self.size) as usize
+    }
+
+    fn value(&mut self, ty: &MetaDataType, depth: usize) -> Value {
+        match ty {
+            MetaDataType::Integer => {
+                Value::from(self.scalar((i32::MIN as i64, i32::MAX as i64), depth))
+            }
+            MetaDataType::Long => Value::from(self.scalar((i64::MIN / 2, i64::MAX / 2), depth)),
+            MetaDataType::Double => {
+                let v = self.int((-1000, 1000)) as f64 + self.rng.gen::<f64>();
+                Value::from((v * 1e5).round() / 1e5)
+            }
+            MetaDataType::Bool => Value::from(self.rng.gen::<bool>()),
+            MetaDataType::Character => Value::from(self.char().to_string()),
+            MetaDataType::String => {
+                let len = self.len(depth);
+                Value::from((0..len).map(|_| self.char()).collect::<String>())
+            }
+            MetaDataType::ListNode => {
+                let len = self.len(depth);
+                Value::from((0..len).map(|_| self.int((-100, 100))).collect::<Vec<_>>())
+            }
+            MetaDataType::TreeNode => {
+                let len = self.len(depth);
+                self.tree(len)
+            }
+            MetaDataType::List(t) => {
+                let len = self.lengths.get(depth).copied().unwrap_or_default();
+                let mut items = vec![];
+                let mut seen = HashSet::new();
+                // a few tries to draw distinct elements
+                for _ in 0..len * 10 {
+                    if items.len() == len {
+                        break;
+                    }
+                    let item = self.value(t, depth + 1);
+                    if depth > 0 || !self.c.unique || seen.insert(item.to_string()) {
+                        items.push(item);
+                    }
+                }
+                if depth == 0 && self.c.sorted {
+                    items.sort_by(cmp_values);
+                }
+                Value::Array(items)
+            }
+            _ => Value::Null,
+        }
+    }
+
+    /// A random shape of `len` nodes in level order.
+    fn tree(&mut self, len: usize) -> Value {
+        let mut children: Vec<[Option<usize>; 2]> = vec![];
+        for i in 0..len {
+            children.push([None, None]);
+            if i == 0 {
+                continue;
+            }
+            // walk down from the root to a free slot
+            let mut node = 0;
+            loop {
+                let side = self.rng.gen_range(0, 2);
+                match children[node][side] {
+                    Some(child) => node = child,
+                    None => {
+                        children[node][side] = Some(i);
+                        break;
+                    }
+                }
+            }
+        }
+        let mut values = vec![];
+        let mut queue = std::collections::VecDeque::from([(len > 0).then_some(0)]);
+        while let Some(node) = queue.pop_front() {
+            match node {
+                Some(node) => {
+                    values.push(Value::from(self.int((-100, 100))));
+                    queue.extend(children[node]);
+                }
+                None => values.push(Value::Null),
+            }
+        }
+        while values.last() == Some(&Value::Null) {
+            values.pop();
+        }
+        Value::Array(values)
+    }
+}
+
+fn leaves(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().flat_map(leaves).collect(),
+        v => vec![v],
+    }
+}
+
+fn is_unique(items: &[Value]) -> bool {
+    let mut seen = HashSet::new();
+    items.iter().all(|item| seen.insert(item.to_string()))
+}
+
+fn cmp_values(a: &Value, b: &Value) -> std::cmp::Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+/// Smaller variants of the inputs: fewer elements first, then values closer to zero.
+fn shrink_candidates(inputs: &[Value]) -> Vec<Vec<Value>> {
+    let mut candidates = vec![];
+    for (i, value) in inputs.iter().enumerate() {
+        let mut with = |v: Value| {
+            let mut candidate = inputs.to_vec();
+            candidate[i] = v;
+            candidates.push(candidate);
+        };
+        match value {
+            Value::Array(items) => {
+                let len = items.len();
+                if len > 1 {
+                    with(Value::Array(items[..len / 2].to_vec()));
+                    with(Value::Array(items[len / 2..].to_vec()));
+                }
+                for j in 0..len {
+                    let mut items = items.clone();
+                    items.remove(j);
+                    with(Value::Array(items));
+                }
+                for j in 0..len {
+                    for v in shrink_scalar(&items[j]) {
+                        let mut items = items.clone();
+                        items[j] = v;
+                        with(Value::Array(items));
+                    }
+                }
+            }
+            Value::String(s) => {
+                let chars = s.chars().collect::<Vec<_>>();
+                for j in 0..chars.len() {
+                    let mut chars = chars.clone();
+                    chars.remove(j);
+                    with(Value::from(chars.into_iter().collect::<String>()));
+                }
+            }
+            v => shrink_scalar(v).into_iter().for_each(with),
+        }
+    }
+    candidates
+}
+
+fn shrink_scalar(value: &Value) -> Vec<Value> {
+    match value.as_i64() {
+        Some(0) | None => vec![],
+        Some(v) => vec![
+            Value::from(0),
+            Value::from(v / 2),
+            Value::from(v - v.signum()),
+        ],
+    }
+}
+
+fn panic_message(err: Box<dyn std::any::Any + Send>) -> String {
+    match err.downcast::<String>() {
+        Ok(s) => *s,
+        Err(err) => match err.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "panicked".to_owned(),
+        },
+    }
+}
+
+enum Outcome {
+    Pass,
+    /// The brute force can't handle the input, it is skipped.
+    Skip(String),
+    /// Expected and actual result.
+    Fail(Value, String),
+}
+
+fn run_case(
+    inputs: &[Value],
+    solve: &dyn Fn(&[Value]) -> Value,
+    brute: &dyn Fn(&[Value]) -> Value,
+    check: fn(&[Value], &Value, &Value) -> bool,
+) -> Outcome {
+    let expected = match panic::catch_unwind(AssertUnwindSafe(|| brute(inputs))) {
+        Ok(expected) => expected,
+        Err(err) => return Outcome::Skip(panic_message(err)),
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| solve(inputs))) {
+        Ok(actual) if check(inputs, &expected, &actual) => Outcome::Pass,
+        Ok(actual) => Outcome::Fail(expected, actual.to_string()),
+        Err(err) => Outcome::Fail(expected, format!("panicked: {}", panic_message(err))),
+    }
+}
+
+/// Run `solve` and `brute` on random inputs of `params` within `constraints`, comparing their
+/// results with `check`. `LEETCODE_STRESS_RUNS` (default 1000) and `LEETCODE_STRESS_SEED` tune
+/// the run; the smallest failing input found is printed in the testcases file format.
+pub fn stress(
+    params: &[(&str, &str)],
+    constraints: &[&str],
+    solve: impl Fn(&[Value]) -> Value,
+    brute: impl Fn(&[Value]) -> Value,
+    check: fn(&[Value], &Value, &Value) -> bool,
+) {
+    let env = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+    let runs = env("LEETCODE_STRESS_RUNS").unwrap_or(1000);
+    let seed = env("LEETCODE_STRESS_SEED").unwrap_or_else(|| rand::thread_rng().gen());
+    let mut gen = InputGen::new(params, constraints, seed);
+
+    // the panic hook is global, stress tests silence it one at a time
+    static HOOK: Mutex<()> = Mutex::new(());
+    let _guard = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let (mut passed, mut skipped, mut failure) = (0, None, None);
+    for run in 0..runs {
+        let size = 1 + (run * 20 / runs.max(1)) as i64;
+        let inputs = gen.generate(size);
+        match run_case(&inputs, &solve, &brute, check) {
+            Outcome::Pass => passed += 1,
+            Outcome::Skip(err) => skipped = Some(err),
+            Outcome::Fail(expected, actual) => {
+                failure = Some((run, inputs, expected, actual));
+                break;
+            }
+        }
+    }
+    let failure = failure.map(|(run, mut inputs, mut expected, mut actual)| {
+        // keep the first smaller variant that still fails, until none does
+        let mut tries = 0;
+        'shrink: while tries < 10000 {
+            for candidate in shrink_candidates(&inputs) {
+                tries += 1;
+                if !gen.is_valid(&candidate) {
+                    continue;
+                }
+                if let Outcome::Fail(e, a) = run_case(&candidate, &solve, &brute, check) {
+                    (inputs, expected, actual) = (candidate, e, a);
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        (run, inputs, expected, actual)
+    });
+    panic::set_hook(hook);
+
+    if let Some((run, inputs, expected, actual)) = failure {
+        let case = inputs
+            .iter()
+            .chain(std::iter::once(&expected))
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        panic!(
+            "run {run} (LEETCODE_STRESS_SEED={seed}) fails, smallest input found:\n\n{case}\n\n  actual: {actual}\nexpected: {expected}"
+        );
+    }
+    match skipped {
+        Some(err) if passed == 0 => panic!("brute force failed on every input: {err}"),
+        _ => println!("{passed} of {runs} runs passed (LEETCODE_STRESS_SEED={seed})"),
+    }
+}
+
+#[test]
+fn generate_inputs() {
+    let mut gen = InputGen::new(
+        &[
+            ("nums", "integer[]"),
+            ("k", "integer"),
+            ("grid", "character[][]"),
+        ],
+        &[
+            "2 <= nums.length <= 10^4",
+            "-10 <= nums[i] <= 10",
+            "nums 中的所有整数互不相同",
+            "1 <= k <= nums.length",
+            "grid[i].length == 3",
+            "grid[i][j] is '0' or '1'",
+        ],
+        7,
+    );
+    for size in 1..20 {
+        let inputs = gen.generate(size);
+        let nums = inputs[0].as_array().unwrap();
+        assert!(nums.len() >= 2 && is_unique(nums));
+        assert!(nums
+            .iter()
+            .all(|v| (-10..=10).contains(&v.as_i64().unwrap())));
+        let k = inputs[1].as_i64().unwrap();
+        assert!(1 <= k && k <= nums.len() as i64);
+        for row in inputs[2].as_array().unwrap() {
+            let row = row.as_array().unwrap();
+            assert_eq!(row.len(), 3);
+            assert!(row.iter().all(|c| c == "0" || c == "1"));
+        }
+        assert!(gen.is_valid(&inputs));
+    }
+}
+
+#[test]
+fn stress_shrinks() {
+    // wrong for any element above 5
+    let result = panic::catch_unwind(|| {
+        stress(
+            &[("nums", "integer[]")],
+            &["1 <= nums.length <= 100", "0 <= nums[i] <= 100"],
+            |inputs| {
+                Value::from(
+                    leaves(&inputs[0])
+                        .iter()
+                        .filter_map(|v| v.as_i64())
+                        .map(|v| v.min(5))
+                        .sum::<i64>(),
+                )
+            },
+            |inputs| {
+                Value::from(
+                    leaves(&inputs[0])
+                        .iter()
+                        .filter_map(|v| v.as_i64())
+                        .sum::<i64>(),
+                )
+            },
+            |_, expected, actual| expected == actual,
+        )
+    });
+    let message = panic_message(result.unwrap_err());
+    assert!(
+        message.contains("smallest input found:\n\n[6]\n6\n"),
+        "{message}"
+    );
+}
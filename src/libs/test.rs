@@ -1,43 +1,464 @@
 use serde_json::Value;
 
+use super::{
+    json::ToLeetcodeJson,
+    memory::{measure_memory, report_memory},
+};
+
 pub trait TestObject {
     fn call(&mut self, method: &str, args: &[Value]) -> Option<Value>;
 }
 
-fn into_array(value: Value) -> Vec<Value> {
+fn into_array(value: Value, what: &str) -> Vec<Value> {
     match value {
         Value::Array(a) => a,
-        _ => unreachable!(),
+        v => panic!("{what} should be an array, found {v}"),
+    }
+}
+
+/// Format rows as a table with aligned columns.
+pub(crate) fn format_table(rows: &[Vec<String>]) -> String {
+    let mut widths = vec![0; rows.iter().map(Vec::len).max().unwrap_or_default()];
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
     }
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths.iter())
+                .map(|(cell, w)| format!("{cell:w$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
+/// Run every call of a design problem example and print a table of the results, then fail
+/// if any result differs from `excepts`. `null` in `excepts` means the call returns nothing;
+/// when `excepts` itself is `null` the results are only printed.
 pub fn assert_object<O: TestObject>(mut obj: O, methods: Value, params: Value, excepts: Value) {
-    let iter = into_array(methods)
+    let check = !excepts.is_null();
+    let excepts = match excepts {
+        Value::Null => vec![],
+        v => into_array(v, "excepts"),
+    };
+    let methods = into_array(methods, "methods");
+    let params = into_array(params, "params");
+    assert_eq!(
+        methods.len(),
+        params.len(),
+        "methods and params differ in length"
+    );
+
+    let mut rows = vec![[
+        "", "step", "method", "args", "expected", "actual", "elapsed",
+    ]
+    .map(String::from)
+    .to_vec()];
+    let calls = methods
         .into_iter()
-        .map(|v| match v {
-            Value::String(s) => s,
-            _ => unreachable!(),
+        .zip(params)
+        .map(|(m, p)| {
+            let method = match m {
+                Value::String(s) => s,
+                v => v.to_string(),
+            };
+            (method, into_array(p, "params of a call"))
         })
-        .zip(into_array(params).into_iter().map(into_array))
-        .zip(into_array(excepts).into_iter().map(|v| match v {
-            Value::String(s) => s,
-            _ => unreachable!(),
-        }));
-
-    for (i, bb) in iter.enumerate() {
-        let ((m, p), e) = bb;
-        let now = std::time::Instant::now();
-        let result = obj.call(&m, &p);
+        .collect::<Vec<_>>();
+    // the calls are measured apart from the table
+    let (results, memory) = measure_memory(|| {
+        calls
+            .iter()
+            .map(|(method, args)| {
+                let now = std::time::Instant::now();
+                let actual = obj.call(method, args).unwrap_or(Value::Null);
+                (actual, now.elapsed())
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut mismatches = vec![];
+    for (i, ((method, args), (actual, elapsed))) in calls.into_iter().zip(results).enumerate() {
+        let expected = excepts.get(i).cloned().unwrap_or_default();
+
+        // the first call is the constructor
+        let ok = !check || i == 0 || actual == expected;
+        if !ok {
+            mismatches.push(i);
+        }
+        rows.push(vec![
+            if ok { "" } else { "✗" }.to_owned(),
+            i.to_string(),
+            method,
+            Value::Array(args).to_string(),
+            if check {
+                expected.to_string()
+            } else {
+                "?".to_owned()
+            },
+            actual.to_string(),
+            format!("{elapsed:?}"),
+        ]);
+    }
+
+    println!("{}", format_table(&rows));
+    if let Some(memory) = memory {
+        report_memory("calls", memory);
+    }
+    assert!(
+        mismatches.is_empty(),
+        "{} of {} calls mismatch, at step {:?}",
+        mismatches.len(),
+        rows.len() - 1,
+        mismatches
+    );
+}
+
+/// One example of a testcase file: the parameters and, when known, the expected output.
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub index: usize,
+    pub inputs: Vec<Value>,
+    pub expected: Option<Value>,
+}
+
+impl TestCase {
+    fn inputs_str(&self) -> String {
+        self.inputs
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    fn check(&self, actual: Value, eq: impl Fn(&Value, &Value) -> bool) {
+        match self.expected {
+            Some(ref expected) => assert!(
+                eq(&actual, expected),
+                "case {}: {}\n  actual: {}\nexpected: {}",
+                self.index,
+                self.inputs_str(),
+                actual,
+                expected
+            ),
+            None => println!("case {}: {} --> {}", self.index, self.inputs_str(), actual),
+        }
+    }
+
+    /// Run the solution on the case, printing the memory it allocates when `CountingAlloc` is
+    /// the global allocator, and failing above `LEETCODE_MEMORY_LIMIT_MB` (default 256).
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> T {
+        let (value, memory) = measure_memory(f);
+        if let Some(memory) = memory {
+            report_memory(&format!("case {}", self.index), memory);
+        }
+        value
+    }
+
+    /// Compare the result with the expected output in LeetCode's JSON form, so lists and
+    /// trees are reported as `[1,2,3]` rather than nested nodes.
+    pub fn assert<T: ToLeetcodeJson>(&self, actual: T) {
+        self.check(actual.to_leetcode_json(), |a, b| a == b)
+    }
+
+    /// Print the result next to the expected output, for answers checked by hand.
+    pub fn print<T: ToLeetcodeJson>(&self, actual: T) {
+        let expected = self.expected.as_ref().map(Value::to_string);
         println!(
-            "{}. call {} {:?} --> {:?}, used {:?}",
-            i,
-            m,
-            p,
-            result,
-            now.elapsed()
+            "case {}: {}\n  actual: {}\nexpected: {}",
+            self.index,
+            self.inputs_str(),
+            actual.to_leetcode_json(),
+            expected.as_deref().unwrap_or("?")
         );
-        if let Some(r) = result {
-            assert_eq!(r, e);
+    }
+
+    pub fn assert_approx<T: ToLeetcodeJson>(&self, actual: T) {
+        self.check(actual.to_leetcode_json(), approx_eq)
+    }
+
+    pub fn assert_unordered<T: ToLeetcodeJson>(&self, actual: T) {
+        self.check(actual.to_leetcode_json(), unordered_eq)
+    }
+
+    /// Accept the result when `check(inputs, expected, actual)` holds, see [`Checker`].
+    pub fn assert_by<T: ToLeetcodeJson>(
+        &self,
+        actual: T,
+        check: fn(&[Value], &Value, &Value) -> bool,
+    ) {
+        self.check(actual.to_leetcode_json(), |actual, expected| {
+            check(&self.inputs, expected, actual)
+        })
+    }
+}
+
+/// Decides whether an answer is accepted. Generated files contain `impl Checker for Solution`,
+/// override `check` there for problems accepting more than one answer.
+pub trait Checker {
+    fn check(_inputs: &[Value], expected: &Value, actual: &Value) -> bool {
+        actual == expected
+    }
+}
+
+/// Compare with LeetCode's rule for floating point answers: numbers within 1e-5 are equal.
+pub fn approx_eq(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => (a - b).abs() <= 1e-5,
+            _ => a == b,
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| approx_eq(a, b))
+        }
+        _ => actual == expected,
+    }
+}
+
+/// Compare ignoring the order of list elements, at every level of nesting.
+pub fn unordered_eq(actual: &Value, expected: &Value) -> bool {
+    sorted(actual) == sorted(expected)
+}
+
+fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Array(a) => {
+            let mut items = a.iter().map(sorted).collect::<Vec<Value>>();
+            items.sort_by_cached_key(Value::to_string);
+            Value::Array(items)
         }
+        _ => value.clone(),
     }
 }
+
+/// Parse a testcase file: one JSON value per line, `params_len` inputs followed by the
+/// expected output. Cases may be separated by blank lines; a case without its expected
+/// output is only printed. Lines starting with `#` are comments.
+// `usize::is_multiple_of` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+pub fn parse_testcases(content: &str, params_len: usize) -> Vec<TestCase> {
+    let mut cases = vec![];
+    let mut block: Vec<Value> = vec![];
+    // 1-based line of the first value of the block
+    let mut block_start = 0;
+    let lines = content
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with('#'))
+        .chain(std::iter::once((content.lines().count(), "")));
+
+    for (no, line) in lines {
+        if !line.is_empty() {
+            let value = serde_json::from_str(line)
+                .unwrap_or_else(|err| panic!("line {}: invalid json `{}`: {}", no + 1, line, err));
+            if block.is_empty() {
+                block_start = no + 1;
+            }
+            block.push(value);
+            continue;
+        }
+        if block.is_empty() {
+            continue;
+        }
+        if block.len() == params_len {
+            cases.push(TestCase {
+                index: cases.len(),
+                inputs: std::mem::take(&mut block),
+                expected: None,
+            });
+        } else if block.len() % (params_len + 1) == 0 {
+            for mut chunk in block.chunks(params_len + 1).map(|c| c.to_vec()) {
+                let expected = chunk.pop();
+                cases.push(TestCase {
+                    index: cases.len(),
+                    inputs: chunk,
+                    expected,
+                });
+            }
+            block.clear();
+        } else {
+            panic!(
+                "line {}: expect {} inputs and an output per case, found {} lines",
+                block_start,
+                params_len,
+                block.len()
+            );
+        }
+    }
+    cases
+}
+
+pub fn load_testcases(path: &str, params_len: usize) -> Vec<TestCase> {
+    let content = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("can not read testcases {}: {}", path, err));
+    parse_testcases(&content, params_len)
+}
+
+#[test]
+fn testcases() {
+    let cases = parse_testcases(
+        "# nums, target\n[2,7,11,15]\n9\n[0,1]\n[3,2,4]\n6\n[1,2]\n\n[3,3]\n6\n",
+        2,
+    );
+    assert_eq!(cases.len(), 3);
+    assert_eq!(
+        cases[1].inputs,
+        vec![serde_json::json!([3, 2, 4]), serde_json::json!(6)]
+    );
+    assert_eq!(cases[1].expected, Some(serde_json::json!([1, 2])));
+    assert_eq!(cases[2].expected, None);
+    cases[0].assert(vec![0, 1]);
+}
+
+#[test]
+fn testcases_line_numbers() {
+    let result = std::panic::catch_unwind(|| parse_testcases("# nums\n# target\n[1]\n{", 1));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.starts_with("line 4: invalid json `{`"), "{message}");
+
+    let result =
+        std::panic::catch_unwind(|| parse_testcases("# nums, target\n\n[1]\n2\n3\n4\n", 2));
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(
+        *message,
+        "line 3: expect 2 inputs and an output per case, found 4 lines"
+    );
+}
+
+#[test]
+#[should_panic(expected = "case 0: [1,2,3]\n  actual: [3,2,1]\nexpected: [1,2,3]")]
+fn assert_list() {
+    use super::list::ListNode;
+    let case = &parse_testcases("[1,2,3]\n[1,2,3]", 1)[0];
+    case.assert(ListNode::from_iter(vec![3, 2, 1]));
+}
+
+#[cfg(test)]
+struct Counter(i32);
+
+#[cfg(test)]
+impl TestObject for Counter {
+    fn call(&mut self, method: &str, args: &[Value]) -> Option<Value> {
+        match method {
+            "add" => self.0 += args[0].as_i64().unwrap() as i32,
+            "get" => return Some(Value::from(self.0)),
+            "isZero" => return Some(Value::from(self.0 == 0)),
+            _ => {}
+        }
+        None
+    }
+}
+
+#[test]
+fn object() {
+    use serde_json::json;
+    assert_object(
+        Counter(0),
+        json!(["Counter", "isZero", "add", "get", "add", "get"]),
+        json!([[], [], [2], [], [3], []]),
+        json!([null, true, null, 2, null, 5]),
+    );
+    assert_object(
+        Counter(0),
+        json!(["Counter", "get"]),
+        json!([[], []]),
+        Value::Null,
+    );
+}
+
+#[test]
+#[should_panic(expected = "2 of 5 calls mismatch, at step [2, 4]")]
+fn object_mismatch() {
+    use serde_json::json;
+    assert_object(
+        Counter(0),
+        json!(["Counter", "add", "get", "add", "get"]),
+        json!([[], [2], [], [3], []]),
+        json!([null, null, 3, null, 6]),
+    );
+}
+
+#[cfg(test)]
+struct MinStack(Vec<(i32, i32)>);
+
+#[cfg(test)]
+#[crate::prelude::test_object]
+impl MinStack {
+    fn new() -> Self {
+        MinStack(vec![])
+    }
+
+    fn push(&mut self, val: i32) {
+        let min = self.0.last().map_or(val, |&(_, min)| min.min(val));
+        self.0.push((val, min));
+    }
+
+    #[allow(clippy::unused_unit)]
+    fn pop(&mut self) -> () {
+        self.0.pop();
+    }
+
+    fn get_min(&self) -> i32 {
+        self.0.last().unwrap().1
+    }
+}
+
+#[test]
+fn test_object_attr() {
+    use serde_json::json;
+    assert_object(
+        MinStack::new(),
+        json!(["MinStack", "push", "push", "push", "getMin", "pop", "get_min"]),
+        json!([[], [-2], [0], [-3], [], [], []]),
+        json!([null, null, null, null, -3, null, -2]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "unknown method top")]
+fn test_object_unknown_method() {
+    use serde_json::json;
+    assert_object(
+        MinStack::new(),
+        json!(["MinStack", "push", "top"]),
+        json!([[], [1], []]),
+        json!([null, null, 1]),
+    );
+}
+
+#[test]
+fn unordered() {
+    use serde_json::json;
+    assert!(unordered_eq(
+        &json!([[1, -1, 0], [2, -1, -1]]),
+        &json!([[-1, -1, 2], [-1, 0, 1]])
+    ));
+    assert!(!unordered_eq(&json!([1, 2]), &json!([1, 2, 2])));
+}
+
+#[test]
+fn checker() {
+    struct Solution;
+    impl Checker for Solution {
+        fn check(inputs: &[Value], _expected: &Value, actual: &Value) -> bool {
+            actual.as_i64() < inputs[0].as_i64()
+        }
+    }
+    let case = &parse_testcases("5\n4", 1)[0];
+    case.assert_by(3, <Solution as Checker>::check);
+}
+
+#[test]
+fn approx() {
+    use serde_json::json;
+    assert!(approx_eq(&json!([2.000001, 0.5]), &json!([2.0, 0.5])));
+    assert!(!approx_eq(&json!(2.0001), &json!(2.0)));
+    assert!(approx_eq(&json!(3), &json!(3.0)));
+}
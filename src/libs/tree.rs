@@ -1,15 +1,19 @@
-use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+use std::{cell::RefCell, collections::VecDeque, fmt, rc::Rc};
 
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+
+/// LeetCode's `TreeNode` by default, with any value type for practice trees.
 #[derive(PartialEq, Eq)]
-pub struct TreeNode {
-    pub val: i32,
-    pub left: Option<Rc<RefCell<TreeNode>>>,
-    pub right: Option<Rc<RefCell<TreeNode>>>,
+pub struct TreeNode<T = i32> {
+    pub val: T,
+    pub left: Option<Rc<RefCell<TreeNode<T>>>>,
+    pub right: Option<Rc<RefCell<TreeNode<T>>>>,
 }
 
-impl TreeNode {
+impl<T> TreeNode<T> {
     #[inline]
-    pub fn new(val: i32) -> Self {
+    pub fn new(val: T) -> Self {
         TreeNode {
             val,
             left: None,
@@ -17,42 +21,140 @@ impl TreeNode {
         }
     }
 
+    pub fn from_vec(values: Vec<Option<T>>) -> Option<Rc<RefCell<TreeNode<T>>>> {
+        build_tree(values)
+    }
+}
+
+impl TreeNode {
+    /// Panics on anything but a level order array of `i32`s and `null`s.
     pub fn from_jsonstr(s: &str) -> Option<Rc<RefCell<TreeNode>>> {
-        let value = serde_json::from_str::<serde_json::Value>(s).ok()?;
-        if let serde_json::Value::Array(array) = value {
-            build_tree(
-                array
-                    .into_iter()
-                    .map(|item| item.as_i64().map(|v| v as i32)),
-            )
-        } else {
-            None
+        Self::try_from_jsonstr(s).unwrap_or_else(|e| panic!("{e:#}"))
+    }
+}
+
+impl<T: DeserializeOwned> TreeNode<T> {
+    /// Parse the level order form, `[3,9,20,null,null,15,7]`, failing on values that are not
+    /// `T` instead of dropping them.
+    pub fn try_from_jsonstr(s: &str) -> Result<Option<Rc<RefCell<TreeNode<T>>>>> {
+        let values = serde_json::from_str::<Option<Vec<Option<T>>>>(s)
+            .with_context(|| format!("invalid tree `{s}`"))?;
+        Ok(build_tree(values.unwrap_or_default()))
+    }
+}
+
+impl<T: Clone> TreeNode<T> {
+    /// Level order values of the subtree in LeetCode's form.
+    pub fn to_vec(&self) -> Vec<Option<T>> {
+        let mut list = vec![Some(self.val.clone())];
+        let mut queue = VecDeque::from([self.left.clone(), self.right.clone()]);
+        while let Some(node) = queue.pop_front() {
+            match node {
+                Some(node) => {
+                    let node = node.borrow();
+                    list.push(Some(node.val.clone()));
+                    queue.push_back(node.left.clone());
+                    queue.push_back(node.right.clone());
+                }
+                None => list.push(None),
+            }
+        }
+        while let Some(None) = list.last() {
+            let _ = list.pop();
         }
+        list
     }
 }
 
-impl std::fmt::Debug for TreeNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            // "[ val: {}, left: {}, right: {} ]",
-            "{} ({}, {})",
-            self.val,
-            match self.left {
-                Some(ref l) => format!("{:?}", l.borrow()),
-                None => String::from("null"),
-            },
-            match self.right {
-                Some(ref l) => format!("{:?}", l.borrow()),
-                None => String::from("null"),
+impl<T: fmt::Display> TreeNode<T> {
+    fn draw(&self, prefix: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.val)?;
+        if self.left.is_none() && self.right.is_none() {
+            return Ok(());
+        }
+        for (child, last) in [(&self.left, false), (&self.right, true)] {
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            write!(f, "{prefix}{branch}")?;
+            match child {
+                Some(child) => child.borrow().draw(&format!("{prefix}{indent}"), f)?,
+                None => writeln!(f, "null")?,
             }
-        )
+        }
+        Ok(())
+    }
+}
+
+/// The level order form, `[3,9,20,null,null,15,7]`.
+impl<T: Clone + fmt::Debug> fmt::Debug for TreeNode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self
+            .to_vec()
+            .iter()
+            .map(|v| match v {
+                Some(v) => format!("{v:?}"),
+                None => "null".to_owned(),
+            })
+            .collect::<Vec<_>>();
+        write!(f, "[{}]", values.join(","))
+    }
+}
+
+/// Draw the tree, left child first:
+///
+/// ```text
+/// 3
+/// ├── 9
+/// └── 20
+///     ├── 15
+///     └── 7
+/// ```
+impl<T: fmt::Display> fmt::Display for TreeNode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.draw("", f)
     }
 }
 
+impl<T: Clone> From<&TreeNode<T>> for Vec<Option<T>> {
+    fn from(node: &TreeNode<T>) -> Self {
+        node.to_vec()
+    }
+}
+
+impl<T: Clone + serde::Serialize> serde::Serialize for TreeNode<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_vec().serialize(serializer)
+    }
+}
+
+/// From the level order form, the root can not be `null`.
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for TreeNode<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<Option<T>>::deserialize(deserializer)?;
+        let root = build_tree(values).ok_or_else(|| serde::de::Error::custom("empty tree"))?;
+        let root = Rc::try_unwrap(root)
+            .map_err(|_| serde::de::Error::custom("shared root"))?
+            .into_inner();
+        Ok(root)
+    }
+}
+
+/// `tree![3, 9, 20, null, null, 15, 7]`, a tree in LeetCode's level order form.
+#[macro_export]
+macro_rules! tree {
+    ($($x:expr),* $(,)?) => {{
+        #[allow(non_upper_case_globals, unused)]
+        const null: Option<i32> = None;
+        $crate::prelude::build_tree::<i32, _>([$(Option::<i32>::from($x)),*])
+    }};
+}
+
 pub type TreeNodeW = Rc<RefCell<TreeNode>>;
 
-fn map_val(val: Option<Option<i32>>) -> Option<Rc<RefCell<TreeNode>>> {
+fn map_val<T>(val: Option<Option<T>>) -> Option<Rc<RefCell<TreeNode<T>>>> {
     val.and_then(|item| item.map(|val| Rc::new(RefCell::new(TreeNode::new(val)))))
 }
 
@@ -67,13 +169,15 @@ impl RawTree {
     }
 }
 
-pub fn build_tree<I: IntoIterator<Item = Option<i32>>>(list: I) -> Option<TreeNodeW> {
+pub fn build_tree<T, I: IntoIterator<Item = Option<T>>>(
+    list: I,
+) -> Option<Rc<RefCell<TreeNode<T>>>> {
     let mut iter = list.into_iter();
     let root = match iter.next() {
         Some(Some(val)) => Rc::new(RefCell::new(TreeNode::new(val))),
         _ => return None,
     };
-    let mut queue: VecDeque<TreeNodeW> = VecDeque::new();
+    let mut queue = VecDeque::new();
     queue.push_back(root.clone());
     while let Some(node) = queue.pop_front() {
         let left = map_val(iter.next());
@@ -92,37 +196,14 @@ pub fn build_tree<I: IntoIterator<Item = Option<i32>>>(list: I) -> Option<TreeNo
 }
 
 pub fn format_tree(tree: Option<Rc<RefCell<TreeNode>>>) -> Vec<Option<i32>> {
-    let mut list = vec![];
-    if let Some(root) = tree {
-        let mut queue = VecDeque::new();
-        list.push(Some(root.borrow().val));
-        queue.push_back(root);
-        while let Some(node) = queue.pop_front() {
-            let mut node_borrow = node.borrow_mut();
-            match node_borrow.left.take() {
-                Some(left) => {
-                    list.push(Some(left.borrow().val));
-                    queue.push_back(left);
-                }
-                None => {
-                    list.push(None);
-                }
-            }
-            match node_borrow.right.take() {
-                Some(right) => {
-                    list.push(Some(right.borrow().val));
-                    queue.push_back(right);
-                }
-                None => {
-                    list.push(None);
-                }
-            }
-        }
-        while let Some(None) = list.last() {
-            let _ = list.pop();
-        }
-    }
-    list
+    level_order(&tree)
+}
+
+/// Level order values of the tree in LeetCode's form, leaving the tree untouched.
+pub(crate) fn level_order(tree: &Option<TreeNodeW>) -> Vec<Option<i32>> {
+    tree.as_ref()
+        .map(|root| root.borrow().to_vec())
+        .unwrap_or_default()
 }
 
 #[test]
@@ -141,3 +222,66 @@ fn serde_tree2() {
     let list = format_tree(tree);
     assert_eq!(s, serde_json::to_string(&list).unwrap());
 }
+
+#[test]
+fn tree_utils() {
+    let tree = tree![3, 9, 20, null, null, 15, 7];
+    assert_eq!(tree, TreeNode::from_jsonstr("[3,9,20,null,null,15,7]"));
+    // format_tree leaves the tree intact
+    format_tree(tree.clone());
+    assert_eq!(
+        format_tree(tree.clone()),
+        vec![Some(3), Some(9), Some(20), None, None, Some(15), Some(7)]
+    );
+
+    let root = tree.unwrap();
+    let node = root.borrow();
+    assert_eq!(format!("{:?}", node), "[3,9,20,null,null,15,7]");
+    assert_eq!(
+        node.to_string(),
+        "3\n├── 9\n└── 20\n    ├── 15\n    └── 7\n"
+    );
+    assert_eq!(
+        tree![1, null, 2].unwrap().borrow().to_string(),
+        "1\n├── null\n└── 2\n"
+    );
+
+    let s = serde_json::to_string(&*node).unwrap();
+    assert_eq!(s, "[3,9,20,null,null,15,7]");
+    let de: TreeNode = serde_json::from_str(&s).unwrap();
+    assert_eq!(de, *node);
+    assert!(serde_json::from_str::<TreeNode>("[]").is_err());
+    assert_eq!(tree![], None);
+}
+
+#[test]
+fn generic_tree() {
+    let tree = TreeNode::<String>::try_from_jsonstr(r#"["a",null,"b"]"#)
+        .unwrap()
+        .unwrap();
+    assert_eq!(format!("{:?}", tree.borrow()), r#"["a",null,"b"]"#);
+    assert_eq!(tree.borrow().to_string(), "a\n├── null\n└── b\n");
+
+    let tree = TreeNode::from_vec(vec![Some('x'), Some('y')]).unwrap();
+    assert_eq!(tree.borrow().to_vec(), vec![Some('x'), Some('y')]);
+
+    let tree = TreeNode::<i64>::try_from_jsonstr("[1,10000000000]").unwrap();
+    assert_eq!(
+        tree.unwrap().borrow().to_vec(),
+        vec![Some(1), Some(10000000000)]
+    );
+}
+
+#[test]
+fn invalid_tree() {
+    assert!(TreeNode::<i32>::try_from_jsonstr("[1,10000000000]").is_err());
+    assert!(TreeNode::<i32>::try_from_jsonstr("[1,\"a\"]").is_err());
+    assert!(TreeNode::<i32>::try_from_jsonstr("[1,2").is_err());
+    assert_eq!(TreeNode::<i32>::try_from_jsonstr("[]").unwrap(), None);
+}
+
+#[test]
+#[should_panic(expected = "invalid tree `[1,1.5]`")]
+fn invalid_tree_panics() {
+    TreeNode::from_jsonstr("[1,1.5]");
+}
@@ -4,8 +4,9 @@ use anyhow::{bail, Result};
 use clap::Parser;
 use leetcode_tool::{
     fetch,
+    layout::Layout,
     leetcode::{question_of_today, random_question},
-    submit, template,
+    migrate, perf, submit, template,
     util::get_title_slug,
 };
 
@@ -18,6 +19,20 @@ enum Action {
     },
     Submit {
         title: String,
+        /// Submit this entry of `VARIANTS` instead of the first one
+        #[clap(long)]
+        variant: Option<String>,
+    },
+    /// Time the solution on the largest inputs the constraints allow and estimate its complexity,
+    /// in release mode
+    Perf { title: String },
+    /// Move solution files into another layout
+    Migrate {
+        #[clap(long)]
+        to: Layout,
+        /// Print the planned moves without touching any file
+        #[clap(long, action)]
+        dry_run: bool,
     },
     // Login,
 }
@@ -42,7 +57,7 @@ async fn main_inner() -> Result<()> {
                     } else {
                         let question = question_of_today().await?;
                         let title_slug = question
-                            .get(0)
+                            .first()
                             .and_then(|q| q.question.as_ref())
                             .map(|q| &q.titleSlug)
                             .expect("can not find today's question");
@@ -54,7 +69,8 @@ async fn main_inner() -> Result<()> {
             println!("start to fetch project {}", title);
             let question = fetch::fetch_question(&title).await?;
             let project_dir = env::current_dir()?;
-            let file_path = template::w::write_template(&question, project_dir).await?;
+            let file_path =
+                template::w::write_template(&question, project_dir, Layout::from_env()?).await?;
             println!("> {}", file_path.display());
             if !question.hints.is_empty() {
                 for hint in question.hints {
@@ -62,7 +78,7 @@ async fn main_inner() -> Result<()> {
                 }
             }
         }
-        Action::Submit { title } => {
+        Action::Submit { title, variant } => {
             let cookie = match std::env::var("COOKIE") {
                 Ok(c) => c,
                 Err(_) => bail!(
@@ -70,7 +86,25 @@ async fn main_inner() -> Result<()> {
                 ),
             };
             let title = get_title_slug(&title);
-            submit::submit_code(&title, &cookie).await?
+            submit::submit_code(&title, &cookie, variant.as_deref()).await?
+        }
+        Action::Perf { title } => {
+            let title = get_title_slug(&title);
+            perf::run_perf(&env::current_dir()?, &title).await?
+        }
+        Action::Migrate { to, dry_run } => {
+            let project_dir = env::current_dir()?;
+            let moves = migrate::migrate(&project_dir, to, dry_run).await?;
+            for m in moves.iter() {
+                let from = m.from.strip_prefix(&project_dir).unwrap_or(&m.from);
+                let to = m.to.strip_prefix(&project_dir).unwrap_or(&m.to);
+                println!("{} -> {}", from.display(), to.display());
+            }
+            if dry_run {
+                println!("{} file(s) would be moved", moves.len());
+            } else {
+                println!("{} file(s) moved", moves.len());
+            }
         }
     }
     Ok(())
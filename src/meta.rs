@@ -1,3 +1,5 @@
+use crate::constraint::{parse_constraints, Constraints};
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
 pub enum MetaData {
@@ -15,6 +17,62 @@ pub enum MetaData {
     },
 }
 
+impl MetaData {
+    /// Give every `Node` parameter and return type the shape `kind`.
+    pub fn resolve_nodes(&mut self, kind: NodeKind) {
+        let resolve = |t: &mut MetaDataType| *t = t.with_node_kind(kind);
+        match self {
+            MetaData::Base {
+                params, r#return, ..
+            } => {
+                params.iter_mut().for_each(|p| resolve(&mut p.r#type));
+                resolve(&mut r#return.r#type);
+            }
+            MetaData::Class {
+                constructor,
+                methods,
+                ..
+            } => {
+                constructor
+                    .params
+                    .iter_mut()
+                    .for_each(|p| resolve(&mut p.r#type));
+                for method in methods.iter_mut() {
+                    method
+                        .params
+                        .iter_mut()
+                        .for_each(|p| resolve(&mut p.r#type));
+                    resolve(&mut method.r#return.r#type);
+                }
+            }
+        }
+    }
+
+    /// Give every parameter the constraints the statement (HTML) states for its name.
+    pub fn attach_constraints(&mut self, html: &str) {
+        let params: Vec<&mut MetaDataParam> = match self {
+            MetaData::Base { params, .. } => params.iter_mut().collect(),
+            MetaData::Class {
+                constructor,
+                methods,
+                ..
+            } => constructor
+                .params
+                .iter_mut()
+                .chain(methods.iter_mut().flat_map(|m| m.params.iter_mut()))
+                .collect(),
+        };
+        let names = params.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+        let constraints = parse_constraints(html, &names);
+        for param in params {
+            if let Some(c) = constraints.get(&param.name) {
+                param.constraints = c.clone();
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MetaDataConstructor {
     pub params: Vec<MetaDataParam>,
@@ -26,6 +84,9 @@ pub struct MetaDataParam {
     #[serde(rename = "type")]
     #[serde(with = "type_serde")]
     pub r#type: MetaDataType,
+    /// From the statement, filled by [`MetaData::attach_constraints`].
+    #[serde(skip)]
+    pub constraints: Constraints,
 }
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MetaDataReturn {
@@ -36,49 +97,188 @@ pub struct MetaDataReturn {
     pub output: Option<MetaDataReturnOutput>,
     manual: Option<bool>,
 }
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 
-pub struct MetaDataReturnOutput {
-    paramindex: usize,
+impl MetaDataReturn {
+    /// The judge can't compare the output by value, results have to be checked by hand.
+    pub fn is_manual(&self) -> bool {
+        self.manual.unwrap_or(false)
+    }
 }
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MetaDataReturnOutput {
+    /// Index of the parameter modified in place and compared against the expected output.
+    pub paramindex: usize,
+}
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum MetaDataType {
     String,
     Integer,
+    Long,
+    Double,
     TreeNode,
     ListNode,
     Character,
     Void,
     Bool,
+    /// LeetCode's `Node`, its shape depends on the problem and is unknown until resolved
+    /// with [`MetaDataType::with_node_kind`].
+    Node(Option<NodeKind>),
+    NestedInteger,
+    Interval,
     List(Box<MetaDataType>),
     Unknow(String),
 }
 
+/// The problem families that call their node type `Node`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum NodeKind {
+    Graph,
+    Random,
+    Nary,
+    Next,
+    Quad,
+}
+
+/// Tell which `Node` a problem uses from the class definition in its (non-Rust) snippets.
+pub fn node_kind(code: &str) -> Option<NodeKind> {
+    if code.contains("neighbors") {
+        Some(NodeKind::Graph)
+    } else if code.contains("random") {
+        Some(NodeKind::Random)
+    } else if code.contains("children") {
+        Some(NodeKind::Nary)
+    } else if code.contains("topLeft") {
+        Some(NodeKind::Quad)
+    } else if code.contains("next") && code.contains("left") {
+        Some(NodeKind::Next)
+    } else {
+        None
+    }
+}
+
+/// Parse LeetCode's type language: a primitive or node type, `T[]` or `list<T>`,
+/// nested arbitrarily. Anything else (or containing anything else) is kept as `Unknow`.
 pub fn parse_type(s: &str) -> MetaDataType {
-    let val: Result<MetaDataType, ()> = match s {
-        "TreeNode" => Ok(MetaDataType::TreeNode),
-        "ListNode" => Ok(MetaDataType::ListNode),
-        "string" => Ok(MetaDataType::String),
-        "string[]" | "list<string>" => Ok(MetaDataType::List(Box::new(MetaDataType::String))),
-        "string[][]" | "list<list<string>>" => Ok(MetaDataType::List(Box::new(
-            MetaDataType::List(Box::new(MetaDataType::String)),
-        ))),
-        "integer" | "long" => Ok(MetaDataType::Integer),
-        "integer[]" | "list<integer>" | "long[]" | "list<long>" => {
-            Ok(MetaDataType::List(Box::new(MetaDataType::Integer)))
+    let s = s.trim();
+    if let Some(inner) = s.strip_suffix("[]") {
+        return MetaDataType::List(Box::new(parse_type(inner)));
+    }
+    if let Some(inner) = s.strip_prefix("list<").and_then(|s| s.strip_suffix('>')) {
+        return MetaDataType::List(Box::new(parse_type(inner)));
+    }
+    match s {
+        "TreeNode" => MetaDataType::TreeNode,
+        "ListNode" => MetaDataType::ListNode,
+        "string" => MetaDataType::String,
+        "integer" => MetaDataType::Integer,
+        "long" => MetaDataType::Long,
+        "double" => MetaDataType::Double,
+        "boolean" => MetaDataType::Bool,
+        "character" => MetaDataType::Character,
+        "void" => MetaDataType::Void,
+        "Node" => MetaDataType::Node(None),
+        "NestedInteger" => MetaDataType::NestedInteger,
+        "Interval" => MetaDataType::Interval,
+        _ => MetaDataType::Unknow(s.to_owned()),
+    }
+}
+
+impl MetaDataType {
+    /// Whether the type, including every nested element type, is understood.
+    pub fn is_known(&self) -> bool {
+        match self {
+            MetaDataType::List(t) => t.is_known(),
+            MetaDataType::Node(kind) => kind.is_some(),
+            MetaDataType::Unknow(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Replace every `Node` with the given kind.
+    pub fn with_node_kind(&self, kind: NodeKind) -> MetaDataType {
+        match self {
+            MetaDataType::Node(_) => MetaDataType::Node(Some(kind)),
+            MetaDataType::List(t) => MetaDataType::List(Box::new(t.with_node_kind(kind))),
+            t => t.clone(),
+        }
+    }
+
+    pub fn has_node(&self) -> bool {
+        match self {
+            MetaDataType::Node(_) => true,
+            MetaDataType::List(t) => t.has_node(),
+            _ => false,
+        }
+    }
+
+    /// The Rust type LeetCode's Rust snippets use for this type.
+    pub fn rust_type(&self) -> String {
+        match self {
+            MetaDataType::String => "String".to_owned(),
+            MetaDataType::Integer => "i32".to_owned(),
+            MetaDataType::Long => "i64".to_owned(),
+            MetaDataType::Double => "f64".to_owned(),
+            MetaDataType::TreeNode => "Option<Rc<RefCell<TreeNode>>>".to_owned(),
+            MetaDataType::ListNode => "Option<Box<ListNode>>".to_owned(),
+            MetaDataType::Character => "char".to_owned(),
+            MetaDataType::Void => "()".to_owned(),
+            MetaDataType::Bool => "bool".to_owned(),
+            MetaDataType::Node(Some(kind)) => {
+                format!("Option<Rc<RefCell<{}>>>", kind.rust_type())
+            }
+            MetaDataType::Node(None) => "Node".to_owned(),
+            MetaDataType::NestedInteger => "NestedInteger".to_owned(),
+            MetaDataType::Interval => "Interval".to_owned(),
+            MetaDataType::List(t) => format!("Vec<{}>", t.rust_type()),
+            MetaDataType::Unknow(s) => s.clone(),
         }
-        "integer[][]" | "list<list<integer>>" | "long[][]" | "list<list<long>>" => {
-            Ok(MetaDataType::List(Box::new(MetaDataType::List(Box::new(
-                MetaDataType::Integer,
-            )))))
+    }
+
+    /// Whether values of this type are compared with LeetCode's 1e-5 tolerance.
+    pub fn is_float(&self) -> bool {
+        match self {
+            MetaDataType::Double => true,
+            MetaDataType::List(t) => t.is_float(),
+            _ => false,
         }
-        "boolean" => Ok(MetaDataType::Bool),
-        "character" => Ok(MetaDataType::Character),
-        "void" => Ok(MetaDataType::Void),
-        _ => Ok(MetaDataType::Unknow(s.to_owned())),
-    };
-    val.unwrap()
+    }
+}
+
+impl NodeKind {
+    /// The node type of the prelude for this kind.
+    pub fn rust_type(&self) -> &'static str {
+        match self {
+            NodeKind::Graph => "GraphNode",
+            NodeKind::Random => "RandomNode",
+            NodeKind::Nary => "NaryNode",
+            NodeKind::Next => "NextNode",
+            NodeKind::Quad => "QuadNode",
+        }
+    }
+}
+
+impl std::fmt::Display for MetaDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetaDataType::String => write!(f, "string"),
+            MetaDataType::Integer => write!(f, "integer"),
+            MetaDataType::Long => write!(f, "long"),
+            MetaDataType::Double => write!(f, "double"),
+            MetaDataType::TreeNode => write!(f, "TreeNode"),
+            MetaDataType::ListNode => write!(f, "ListNode"),
+            MetaDataType::Character => write!(f, "character"),
+            MetaDataType::Void => write!(f, "void"),
+            MetaDataType::Bool => write!(f, "boolean"),
+            MetaDataType::Node(_) => write!(f, "Node"),
+            MetaDataType::NestedInteger => write!(f, "NestedInteger"),
+            MetaDataType::Interval => write!(f, "Interval"),
+            MetaDataType::List(t) => write!(f, "{t}[]"),
+            MetaDataType::Unknow(s) => write!(f, "{s}"),
+        }
+    }
 }
+
 impl From<&str> for MetaDataType {
     fn from(s: &str) -> Self {
         parse_type(s)
@@ -106,10 +306,7 @@ mod type_serde {
     where
         S: Serializer,
     {
-        // if let Some(ref d) = *t {
-        return s.serialize_str(&format!("{:?}", t));
-        // }
-        // s.serialize_none()
+        s.serialize_str(&t.to_string())
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<MetaDataType, D::Error>
@@ -139,6 +336,71 @@ mod tests {
         println!("{:?}", meta_type);
     }
 
+    #[test]
+    fn test_parse_nested_type() {
+        use MetaDataType::*;
+        let list = |t| List(Box::new(t));
+        assert_eq!(parse_type("character[][]"), list(list(Character)));
+        assert_eq!(parse_type("list<list<character>>"), list(list(Character)));
+        assert_eq!(parse_type("TreeNode[]"), list(TreeNode));
+        assert_eq!(parse_type("list<ListNode>"), list(ListNode));
+        assert_eq!(parse_type("list<integer[]>[]"), list(list(list(Integer))));
+        assert_eq!(parse_type("integer[][][]"), list(list(list(Integer))));
+        assert_eq!(parse_type("Node[]"), list(Node(None)));
+        assert!(!parse_type("list<Node>").is_known());
+        assert_eq!(
+            parse_type("list<Node>").with_node_kind(NodeKind::Nary),
+            list(Node(Some(NodeKind::Nary)))
+        );
+        assert_eq!(parse_type("list<NestedInteger>"), list(NestedInteger));
+        assert_eq!(parse_type("Interval[][]"), list(list(Interval)));
+        assert_eq!(parse_type("Foo"), Unknow("Foo".to_owned()));
+    }
+
+    #[test]
+    fn test_rust_type() {
+        assert_eq!(parse_type("long[]").rust_type(), "Vec<i64>");
+        assert_eq!(
+            parse_type("list<list<double>>").rust_type(),
+            "Vec<Vec<f64>>"
+        );
+        assert!(parse_type("double[][]").is_float());
+        assert_eq!(
+            parse_type("Node")
+                .with_node_kind(NodeKind::Graph)
+                .rust_type(),
+            "Option<Rc<RefCell<GraphNode>>>"
+        );
+    }
+
+    #[test]
+    fn test_type_round_trip() {
+        for s in [
+            "integer[][]",
+            "list<list<character>>",
+            "TreeNode[]",
+            "string",
+            "Node[]",
+            "list<NestedInteger>",
+            "Interval",
+        ] {
+            let param: MetaDataParam =
+                serde_json::from_value(serde_json::json!({"name": "p", "type": s})).unwrap();
+            let json = serde_json::to_value(&param).unwrap();
+            let param2: MetaDataParam = serde_json::from_value(json).unwrap();
+            assert_eq!(param.r#type, param2.r#type);
+        }
+    }
+
+    #[test]
+    fn test_node_kind() {
+        let java = "/*\n// Definition for a Node.\nclass Node {\n    public int val;\n    public List<Node> neighbors;\n}\n*/";
+        assert_eq!(node_kind(java), Some(NodeKind::Graph));
+        let java = "class Node {\n    public int val;\n    public Node left;\n    public Node right;\n    public Node next;\n}";
+        assert_eq!(node_kind(java), Some(NodeKind::Next));
+        assert_eq!(node_kind("class Solution {}"), None);
+    }
+
     #[test]
     fn test_parse() {
         let a = parse_meta("{\n  \"name\": \"buddyStrings\",\n  \"params\": [\n    {\n      \"name\": \"s\",\n      \"type\": \"string\"\n    },\n    {\n      \"name\": \"goal\",\n      \"type\": \"string\"\n    }\n  ],\n  \"return\": {\n    \"type\": \"boolean\"\n  }\n}").unwrap();
@@ -0,0 +1,175 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+use async_std::{fs, process::Command};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    layout::{register_module, unregister_module, Layout},
+    util::parse_src_slug,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub title_slug: String,
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Title slug and difficulty from the doc header written by `fetch`.
+fn parse_header(content: &str) -> Option<(String, String)> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^/// difficulty: `([^`]*)`").unwrap();
+    }
+    let title_slug = content
+        .lines()
+        .find_map(|line| parse_src_slug(line.trim_start()))?
+        .to_owned();
+    let difficulty = content
+        .lines()
+        .find_map(|line| RE.captures(line.trim_start()).and_then(|caps| caps.get(1)))
+        .map(|m| m.as_str().to_owned())
+        .unwrap_or_default();
+    Some((title_slug, difficulty))
+}
+
+fn collect_rs_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rs_files(&path, files)?;
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Find every solution file (recognized by its `/// src:` line) that is not where `to` expects it.
+/// Fails before anything is moved if two files share a destination or a destination exists.
+pub async fn plan(project_dir: &Path, to: Layout) -> Result<Vec<Move>> {
+    let mut files = vec![];
+    collect_rs_files(&project_dir.join("src"), &mut files)?;
+    files.sort();
+
+    let mut moves = vec![];
+    let mut sources: HashMap<PathBuf, PathBuf> = HashMap::new();
+    for from in files {
+        let content = fs::read_to_string(&from).await?;
+        let (title_slug, difficulty) = match parse_header(&content) {
+            Some(h) => h,
+            None => continue,
+        };
+        let title = title_slug.replace('-', "_");
+        let to = to.solution_path(project_dir, &title, &difficulty);
+        if let Some(other) = sources.insert(to.clone(), from.clone()) {
+            bail!(
+                "can not move both {} and {} to {}",
+                other.display(),
+                from.display(),
+                to.display()
+            );
+        }
+        if to != from {
+            if to.exists() {
+                bail!(
+                    "can not move {} to {}: file exists",
+                    from.display(),
+                    to.display()
+                );
+            }
+            moves.push(Move {
+                title_slug,
+                from,
+                to,
+            });
+        }
+    }
+    Ok(moves)
+}
+
+async fn git_mv(project_dir: &Path, from: &Path, to: &Path) -> Result<()> {
+    let status = Command::new("git")
+        .arg("mv")
+        .arg(from)
+        .arg(to)
+        .current_dir(project_dir)
+        .status()
+        .await;
+    match status {
+        Ok(s) if s.success() => {}
+        _ => {
+            log::warn!("`git mv {}` failed, fall back to rename", from.display());
+            fs::rename(from, to).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn migrate(project_dir: &Path, to: Layout, dry_run: bool) -> Result<Vec<Move>> {
+    let moves = plan(project_dir, to).await?;
+    if dry_run {
+        return Ok(moves);
+    }
+    for m in moves.iter() {
+        if let Some(parent) = m.to.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        git_mv(project_dir, &m.from, &m.to).await?;
+        unregister_module(project_dir, &m.from).await?;
+        register_module(project_dir, &m.to).await?;
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        let content = "use leetcode_tool::prelude::*;\n\n/// # 两数之和\n///\n/// src: https://leetcode.cn/problems/two-sum/\n///\n/// difficulty: `Easy`\n";
+        assert_eq!(
+            parse_header(content),
+            Some(("two-sum".to_owned(), "Easy".to_owned()))
+        );
+        assert_eq!(parse_header("pub mod two_sum;"), None);
+    }
+
+    #[test]
+    fn test_plan_conflicts() {
+        let project_dir = std::env::temp_dir().join(format!(
+            "leetcode-tool-plan-conflicts-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&project_dir);
+        let header =
+            "/// src: https://leetcode.cn/problems/two-sum/\n///\n/// difficulty: `Easy`\n";
+        for dir in ["problems", "easy"] {
+            std::fs::create_dir_all(project_dir.join("src").join(dir)).unwrap();
+            std::fs::write(project_dir.join("src").join(dir).join("two_sum.rs"), header).unwrap();
+        }
+
+        async_std::task::block_on(async {
+            let err = migrate(&project_dir, Layout::Flat, false)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().starts_with("can not move both"), "{err}");
+            assert!(project_dir.join("src/easy/two_sum.rs").exists());
+            assert!(project_dir.join("src/problems/two_sum.rs").exists());
+
+            std::fs::remove_file(project_dir.join("src/easy/two_sum.rs")).unwrap();
+            std::fs::write(project_dir.join("src/two_sum.rs"), "").unwrap();
+            let err = migrate(&project_dir, Layout::Flat, false)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().ends_with("file exists"), "{err}");
+            assert!(project_dir.join("src/problems/two_sum.rs").exists());
+        });
+        std::fs::remove_dir_all(&project_dir).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use async_std::process::Command;
+
+use crate::layout::locate_solution;
+
+/// The module path of a solution file: `src/problems/two_sum.rs` is `problems::two_sum`.
+fn module_path(project_dir: &Path, file: &Path) -> Result<String> {
+    let relative = match file.strip_prefix(project_dir.join("src")) {
+        Ok(p) => p.with_extension(""),
+        Err(_) => bail!("invalid solution path {}", file.display()),
+    };
+    Ok(relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("::"))
+}
+
+/// Run the generated `perf_` test and `bench_` benchmark of a solution in release mode.
+pub async fn run_perf(project_dir: &Path, title_slug: &str) -> Result<()> {
+    let title = title_slug.replace('-', "_");
+    let file = match locate_solution(project_dir, &title) {
+        Some(p) => p,
+        None => bail!("can not find solution file of {title_slug}"),
+    };
+    let module = module_path(project_dir, &file)?;
+    let status = Command::new("cargo")
+        .args(["test", "--release", "--lib", "--"])
+        .arg(format!("{module}::perf_"))
+        .arg(format!("{module}::bench_"))
+        .args(["--ignored", "--nocapture", "--test-threads=1"])
+        .current_dir(project_dir)
+        .status()
+        .await?;
+    if !status.success() {
+        bail!("perf test of {title_slug} failed");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_path() {
+        let dir = Path::new("/p");
+        assert_eq!(
+            module_path(dir, Path::new("/p/src/problems/two_sum.rs")).unwrap(),
+            "problems::two_sum"
+        );
+        assert_eq!(
+            module_path(dir, Path::new("/p/src/two_sum.rs")).unwrap(),
+            "two_sum"
+        );
+        assert!(module_path(dir, Path::new("/q/two_sum.rs")).is_err());
+    }
+}
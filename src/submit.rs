@@ -1,19 +1,27 @@
 use std::time::Duration;
 
 use crate::fetch::fetch_question;
+use crate::layout::locate_solution;
 use crate::leetcode::{check_submissions, submit, CheckSubmissionsResponse, SubmitResponse};
-use crate::template::{END_LINE, START_LINE};
-use anyhow::{bail, Ok, Result};
+use crate::template::{END_LINE, START_LINE, TEST_OBJECT_ATTR};
+use crate::testcase::{append_testcase, testcases_path};
+use crate::util::parse_src_slug;
+use anyhow::{anyhow, bail, Ok, Result};
 use async_std::prelude::*;
 use async_std::process::Command;
 use async_std::task::sleep;
 use async_std::{fs::File, io::BufReader, path::Path};
-use regex::Regex;
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::ToTokens;
 
-pub async fn submit_code(title_slug: &str, cookie: &str) -> Result<()> {
+pub async fn submit_code(title_slug: &str, cookie: &str, variant: Option<&str>) -> Result<()> {
     let title = title_slug.replace('-', "_");
-    let file = format!("src/{title}.rs");
-    let (title_slug, code) = read_content(&file).await?;
+    let project_dir = std::env::current_dir()?;
+    let file = match locate_solution(&project_dir, &title) {
+        Some(p) => p.to_string_lossy().into_owned(),
+        None => bail!("can not find solution file of {title_slug}"),
+    };
+    let (title_slug, code) = read_content(&file, variant).await?;
 
     let question = fetch_question(&title_slug).await?;
 
@@ -45,6 +53,13 @@ pub async fn submit_code(title_slug: &str, cookie: &str) -> Result<()> {
                     eprintln!("last_testcase >> {}", a);
                     eprintln!("expected_output >> {}", b);
                     eprintln!("code_output >> {}", c);
+                    if &status_msg == "Wrong Answer" {
+                        let path = testcases_path(&project_dir, &title_slug);
+                        match append_testcase(&path, &a, &b).await {
+                            Result::Ok(_) => eprintln!("add testcase to {}", path.display()),
+                            Err(err) => log::warn!("fail to add testcase: {:?}", err),
+                        }
+                    }
                 }
                 if let Some(compile_error) = full_compile_error {
                     eprintln!(">> {}", compile_error);
@@ -55,10 +70,10 @@ pub async fn submit_code(title_slug: &str, cookie: &str) -> Result<()> {
                 } else {
                     // success
 
-                    Command::new("git").args(&["add", &file]).status().await?;
+                    Command::new("git").args(["add", &file]).status().await?;
 
                     Command::new("git")
-                        .args(&["commit", "-m", &format!("leetcode({title_slug}): {submission_id}, ({status_runtime}, {status_memory})")])
+                        .args(["commit", "-m", &format!("leetcode({title_slug}): {submission_id}, ({status_runtime}, {status_memory})")])
                         .status()
                         .await?;
 
@@ -72,16 +87,19 @@ pub async fn submit_code(title_slug: &str, cookie: &str) -> Result<()> {
     Ok(())
 }
 
-async fn read_content<P: AsRef<Path>>(file: P) -> Result<(String, String)> {
-    lazy_static::lazy_static! {
-        static ref RE: Regex = Regex::new(r"/problems/(\S+)/").unwrap();
-    }
+/// Title slug and code between the Begin/End lines, with `variant` of the `VARIANTS` in place
+/// of the first one when given.
+pub(crate) async fn read_content<P: AsRef<Path>>(
+    file: P,
+    variant: Option<&str>,
+) -> Result<(String, String)> {
     let f = File::open(file).await?;
     let mut buffer_reader = BufReader::new(f);
 
     let mut title_slug = String::new();
     let mut start = false;
     let mut rust_code = String::new();
+    let mut content = String::new();
 
     let mut buf = String::new();
 
@@ -91,15 +109,15 @@ async fn read_content<P: AsRef<Path>>(file: P) -> Result<(String, String)> {
         if len == 0 {
             break;
         }
+        content.push_str(&buf);
 
-        if buf.starts_with("/// src:") {
-            let mut iter = RE.captures_iter(&buf);
-            title_slug = iter.next().unwrap().get(1).unwrap().as_str().to_owned();
+        if let Some(slug) = parse_src_slug(&buf) {
+            title_slug = slug.to_owned();
         } else if buf.starts_with(START_LINE) {
             start = true;
         } else if buf.starts_with(END_LINE) {
             start = false;
-        } else if start {
+        } else if start && buf.trim() != TEST_OBJECT_ATTR {
             rust_code.push_str(&buf);
             // rust_code.push('\n');
         }
@@ -107,9 +125,142 @@ async fn read_content<P: AsRef<Path>>(file: P) -> Result<(String, String)> {
         buf.clear();
     }
 
+    if let Some(variant) = variant {
+        rust_code = select_variant(&content, &rust_code, variant)?;
+    }
     Ok((title_slug, rust_code))
 }
 
+/// `(name, path)` of the `VARIANTS` of a solution file, the submitted one first.
+fn parse_variants(content: &str) -> Result<Vec<(String, syn::Path)>> {
+    let file = syn::parse_file(content)?;
+    let expr = file.items.iter().find_map(|item| match item {
+        syn::Item::Const(c) if c.ident == "VARIANTS" => Some(&*c.expr),
+        _ => None,
+    });
+    let array = match expr {
+        Some(syn::Expr::Reference(r)) => &*r.expr,
+        Some(e) => e,
+        None => bail!("no `VARIANTS` in the solution file"),
+    };
+    let elems = match array {
+        syn::Expr::Array(a) => &a.elems,
+        e => bail!(
+            "`VARIANTS` should be an array, found `{}`",
+            e.to_token_stream()
+        ),
+    };
+    elems
+        .iter()
+        .map(|e| match e {
+            syn::Expr::Tuple(t) => match (t.elems.first(), t.elems.last()) {
+                (
+                    Some(syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(name),
+                        ..
+                    })),
+                    Some(syn::Expr::Path(p)),
+                ) if t.elems.len() == 2 => Ok((name.value(), p.path.clone())),
+                _ => bail!("invalid variant `{}`", e.to_token_stream()),
+            },
+            _ => bail!("invalid variant `{}`", e.to_token_stream()),
+        })
+        .collect()
+}
+
+/// The type and method of a variant path: `Solution2::two_sum`.
+fn type_and_method(path: &syn::Path) -> Result<(Ident, Ident)> {
+    let segments = path.segments.iter().collect::<Vec<_>>();
+    match segments[..] {
+        [.., ty, method] => Ok((ty.ident.clone(), method.ident.clone())),
+        _ => bail!(
+            "variant `{}` should be `Type::method`",
+            path.to_token_stream()
+        ),
+    }
+}
+
+fn rename(tokens: TokenStream, renames: &[(Ident, Ident)]) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|tree| match tree {
+            TokenTree::Ident(ident) => match renames.iter().find(|(from, _)| *from == ident) {
+                Some((_, to)) => TokenTree::Ident(Ident::new(&to.to_string(), ident.span())),
+                None => TokenTree::Ident(ident),
+            },
+            TokenTree::Group(group) => {
+                let mut renamed = Group::new(group.delimiter(), rename(group.stream(), renames));
+                renamed.set_span(group.span());
+                TokenTree::Group(renamed)
+            }
+            tree => tree,
+        })
+        .collect()
+}
+
+/// Rewrite `code` so the selected variant is the `Solution::method` the judge calls: another
+/// type replaces `impl Solution`, another method replaces the method. Comments are dropped.
+fn select_variant(content: &str, code: &str, variant: &str) -> Result<String> {
+    let variants = parse_variants(content)?;
+    let (ty, method) = match variants.first() {
+        Some((_, path)) => type_and_method(path)?,
+        None => bail!("`VARIANTS` is empty"),
+    };
+    let (variant_ty, variant_method) = match variants.iter().find(|(name, _)| name == variant) {
+        Some((_, path)) => type_and_method(path)?,
+        None => {
+            let names = variants
+                .iter()
+                .map(|(name, _)| format!("`{name}`"))
+                .collect::<Vec<_>>();
+            return Err(anyhow!(
+                "unknown variant `{variant}`, expected one of {}",
+                names.join(", ")
+            ));
+        }
+    };
+    if (&variant_ty, &variant_method) == (&ty, &method) {
+        return Ok(code.to_owned());
+    }
+
+    let mut file = syn::parse_file(code)?;
+    let self_ty = |item: &syn::Item| match item {
+        syn::Item::Impl(i) => match &*i.self_ty {
+            syn::Type::Path(p) => p.path.get_ident().cloned(),
+            _ => None,
+        },
+        _ => None,
+    };
+    if !file
+        .items
+        .iter()
+        .any(|item| self_ty(item).as_ref() == Some(&variant_ty))
+    {
+        bail!("`impl {variant_ty}` of variant `{variant}` is not between the Begin/End lines");
+    }
+    if variant_ty != ty {
+        file.items.retain(|item| {
+            self_ty(item).as_ref() != Some(&ty)
+                && !matches!(item, syn::Item::Struct(s) if s.ident == variant_ty)
+        });
+    } else {
+        for item in file.items.iter_mut() {
+            if self_ty(item).as_ref() != Some(&ty) {
+                continue;
+            }
+            if let syn::Item::Impl(i) = item {
+                i.items
+                    .retain(|item| !matches!(item, syn::ImplItem::Fn(f) if f.sig.ident == method));
+            }
+        }
+    }
+    let tokens = rename(
+        file.into_token_stream(),
+        &[(variant_ty, ty), (variant_method, method)],
+    );
+    Ok(prettyplease::unparse(&syn::parse2(tokens)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,10 +268,45 @@ mod tests {
     #[test]
     fn test_read_content() {
         async_std::task::block_on(async {
-            let c = read_content("src/random_pick_with_blacklist.rs")
+            let c = read_content("src/random_pick_with_blacklist.rs", None)
                 .await
                 .unwrap();
             println!("{:?}", c);
         })
     }
+
+    #[test]
+    fn test_select_variant() {
+        let content = r#"
+impl Solution {
+    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> { vec![] }
+    pub fn two_sum_hash(nums: Vec<i32>, target: i32) -> Vec<i32> { vec![0, 1] }
+}
+pub struct Solution2;
+impl Solution2 {
+    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> { Solution2::helper() }
+    fn helper() -> Vec<i32> { vec![1, 0] }
+}
+const VARIANTS: &[(&str, fn(Vec<i32>, i32) -> Vec<i32>)] = &[
+    ("Solution", Solution::two_sum),
+    ("hash", Solution::two_sum_hash),
+    ("Solution2", Solution2::two_sum),
+];
+"#;
+        let code = content.split("const VARIANTS").next().unwrap();
+        assert_eq!(select_variant(content, code, "Solution").unwrap(), code);
+        assert_eq!(
+            select_variant(content, code, "hash").unwrap(),
+            "impl Solution {\n    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {\n        vec![0, 1]\n    }\n}\npub struct Solution2;\nimpl Solution2 {\n    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {\n        Solution2::helper()\n    }\n    fn helper() -> Vec<i32> {\n        vec![1, 0]\n    }\n}\n"
+        );
+        assert_eq!(
+            select_variant(content, code, "Solution2").unwrap(),
+            "impl Solution {\n    pub fn two_sum(nums: Vec<i32>, target: i32) -> Vec<i32> {\n        Solution::helper()\n    }\n    fn helper() -> Vec<i32> {\n        vec![1, 0]\n    }\n}\n"
+        );
+        let err = select_variant(content, code, "dp").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown variant `dp`, expected one of `Solution`, `hash`, `Solution2`"
+        );
+    }
 }
@@ -0,0 +1,9 @@
+use leetcode_tool::prelude::*;
+
+{{doc}}
+{{struct}}
+{{start_line}}
+{{snippet}}
+{{end_line}}
+
+{{test_code}}
\ No newline at end of file
@@ -0,0 +1,276 @@
+use anyhow::{bail, Result};
+use inflector::Inflector;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{FnArg, ImplItem, Item, Pat, ReturnType};
+
+use crate::{guest::Example, meta::MetaData};
+
+use super::w::json_value_tokens;
+
+/// Interactive problems, whose snippets call an API the judge provides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interactive {
+    Guess,
+    BadVersion,
+    MountainArray,
+    ArrayReader,
+    Master,
+    HtmlParser,
+    Robot,
+}
+
+impl Interactive {
+    /// Recognize the problem from the method and the API classes in its metaData, so that
+    /// problems sharing a class name but not its API (e.g. the `ArrayReader` of 1533) are
+    /// left alone.
+    pub fn detect(meta: &MetaData) -> Option<Self> {
+        let (name, params) = match meta {
+            MetaData::Base { name, params, .. } => (name.as_str(), params),
+            MetaData::Class { .. } => return None,
+        };
+        let takes = |class: &str| params.iter().any(|p| p.r#type.to_string() == class);
+        match name {
+            "guessNumber" => Some(Interactive::Guess),
+            "firstBadVersion" => Some(Interactive::BadVersion),
+            "findInMountainArray" if takes("MountainArray") => Some(Interactive::MountainArray),
+            "search" if takes("ArrayReader") => Some(Interactive::ArrayReader),
+            "findSecretWord" if takes("Master") => Some(Interactive::Master),
+            "crawl" if takes("HtmlParser") => Some(Interactive::HtmlParser),
+            "cleanRoom" if takes("Robot") => Some(Interactive::Robot),
+            _ => None,
+        }
+    }
+
+    /// The mock type passed to the solution, `None` for the free APIs.
+    fn mock_type(&self) -> Option<&'static str> {
+        match self {
+            Interactive::Guess | Interactive::BadVersion => None,
+            Interactive::MountainArray => Some("MountainArray"),
+            Interactive::ArrayReader => Some("ArrayReader"),
+            Interactive::Master => Some("Master"),
+            Interactive::HtmlParser => Some("HtmlParser"),
+            Interactive::Robot => Some("Robot"),
+        }
+    }
+
+    /// Names of the example inputs the mock is built from, with their alternatives.
+    fn hidden_inputs(&self) -> &'static [&'static [&'static str]] {
+        match self {
+            Interactive::Guess => &[&["pick"]],
+            Interactive::BadVersion => &[&["bad"]],
+            Interactive::MountainArray => &[&["mountainArr", "array", "mountainArray"]],
+            Interactive::ArrayReader => &[&["secret", "array", "reader"]],
+            Interactive::Master => &[&["secret"], &["words", "wordlist"], &["allowedGuesses"]],
+            Interactive::HtmlParser => &[&["urls"], &["edges"]],
+            Interactive::Robot => &[&["room"], &["row"], &["col"]],
+        }
+    }
+}
+
+/// The method of `impl Solution` in the snippet.
+struct Method {
+    name: syn::Ident,
+    is_unsafe: bool,
+    has_self: bool,
+    /// Name and whether the parameter is the mock.
+    params: Vec<(String, bool)>,
+    returns: bool,
+}
+
+fn parse_method(snippet: &str, mock_type: Option<&str>) -> Result<Method> {
+    let file = syn::parse_file(snippet)?;
+    let method = file
+        .items
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Impl(item) => Some(item.items),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|item| match item {
+            ImplItem::Fn(method) => Some(method.sig),
+            _ => None,
+        });
+    let sig = match method {
+        Some(sig) => sig,
+        None => bail!("no method found in the snippet"),
+    };
+
+    let mut params = vec![];
+    for input in sig.inputs.iter() {
+        if let FnArg::Typed(pat) = input {
+            let name = match &*pat.pat {
+                Pat::Ident(ident) => ident.ident.to_string(),
+                _ => bail!("unsupported parameter in the snippet"),
+            };
+            let ty = &pat.ty;
+            let is_mock = mock_type.is_some_and(|t| quote!(#ty).to_string().contains(t));
+            params.push((name, is_mock));
+        }
+    }
+    Ok(Method {
+        is_unsafe: sig.unsafety.is_some(),
+        has_self: sig.receiver().is_some(),
+        returns: !matches!(sig.output, ReturnType::Default),
+        name: sig.ident,
+        params,
+    })
+}
+
+fn input_value(example: &Example, names: &[&str]) -> Option<TokenStream> {
+    let value = names.iter().find_map(|name| {
+        example
+            .inputs
+            .iter()
+            .find(|(n, _)| n.to_snake_case() == name.to_snake_case())
+            .and_then(|(_, v)| serde_json::from_str(v).ok())
+    })?;
+    let value = json_value_tokens(&value);
+    Some(quote!(FromLeetcodeJson::from_leetcode_json(&json!(#value))))
+}
+
+/// One test per example, the mock built from the example's hidden inputs.
+pub fn generate_test_code(
+    kind: Interactive,
+    snippet: &str,
+    examples: &[Example],
+) -> Result<TokenStream> {
+    let method = parse_method(snippet, kind.mock_type())?;
+    let many = examples.len() > 1;
+    let mut tests = vec![];
+    for example in examples {
+        let hidden = kind
+            .hidden_inputs()
+            .iter()
+            .map(|names| input_value(example, names))
+            .collect::<Option<Vec<_>>>();
+        let args = method
+            .params
+            .iter()
+            .map(|(name, is_mock)| {
+                if *is_mock {
+                    Some(quote!(&mock))
+                } else {
+                    input_value(example, &[name])
+                }
+            })
+            .collect::<Option<Vec<_>>>();
+        let (hidden, args) = match (hidden, args) {
+            (Some(hidden), Some(args)) => (hidden, args),
+            _ => {
+                log::warn!("can not read the inputs of example {}", example.index);
+                continue;
+            }
+        };
+
+        let setup = match kind {
+            Interactive::Guess => quote!(set_guess_pick(#(#hidden),*);),
+            Interactive::BadVersion => quote!(set_bad_version(#(#hidden),*);),
+            _ => {
+                let mock = format_ident!("{}", kind.mock_type().unwrap());
+                quote!(let mock = #mock::new(#(#hidden),*);)
+            }
+        };
+        let name = &method.name;
+        let mut call = if method.has_self {
+            quote!(Solution.#name(#(#args),*))
+        } else {
+            quote!(Solution::#name(#(#args),*))
+        };
+        if method.is_unsafe {
+            call = quote!(unsafe { #call });
+        }
+        let calls = match kind {
+            Interactive::Guess => quote!(api_calls("guess")),
+            Interactive::BadVersion => quote!(api_calls("isBadVersion")),
+            _ => quote!(mock.calls.count()),
+        };
+        let check = match (kind, method.returns, example.output_value()) {
+            (Interactive::Master, _, _) => quote!(assert!(mock.guessed(), "secret not guessed");),
+            (Interactive::Robot, _, _) => quote!(assert!(mock.all_cleaned(), "room not cleaned");),
+            (Interactive::HtmlParser, true, Some(output)) => {
+                let output = json_value_tokens(&output);
+                quote!(assert!(unordered_eq(&res.to_leetcode_json(), &json!(#output)));)
+            }
+            (_, true, Some(output)) => {
+                let output = json_value_tokens(&output);
+                quote!(assert_eq!(res.to_leetcode_json(), json!(#output));)
+            }
+            _ => quote!(),
+        };
+        let run = if method.returns {
+            quote!(let res = #call;)
+        } else {
+            quote!(#call;)
+        };
+
+        let test_name = name.to_string().to_snake_case();
+        let test_name = if many {
+            format_ident!("test_{}_{}", test_name, example.index)
+        } else {
+            format_ident!("test_{}", test_name)
+        };
+        tests.push(quote! {
+            #[test]
+            pub fn #test_name() {
+                #setup
+                #run
+                println!("{} calls", #calls);
+                #check
+            }
+        });
+    }
+
+    let api = match kind {
+        Interactive::BadVersion => quote!(impl BadVersionApi for Solution {}),
+        _ => quote!(),
+    };
+    Ok(quote! {
+        #api
+        #(#tests)*
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mountain_array() {
+        let snippet = "/**\n * // This is the MountainArray's API interface.\n * struct MountainArray;\n * impl MountainArray {\n *     fn get(index:i32)->i32;\n *     fn length()->i32;\n * };\n */\n\nimpl Solution {\n    pub fn find_in_mountain_array(target: i32, mountainArr: &MountainArray) -> i32 {\n        \n    }\n}";
+        let meta = crate::meta::parse_meta(r#"{"name": "findInMountainArray", "params": [{"name": "target", "type": "integer"}, {"name": "mountainArr", "type": "MountainArray"}], "return": {"type": "integer"}}"#).unwrap();
+        let kind = Interactive::detect(&meta).unwrap();
+        assert_eq!(kind, Interactive::MountainArray);
+        let examples = crate::guest::parse_examples("<p><strong>示例 1：</strong></p><pre><strong>输入：</strong>array = [1,2,3,4,5,3,1], target = 3\n<strong>输出：</strong>2</pre>");
+        let code = generate_test_code(kind, snippet, &examples)
+            .unwrap()
+            .to_string();
+        assert!(code.contains("let mock = MountainArray :: new (FromLeetcodeJson :: from_leetcode_json (& json ! ([1 , 2 , 3 , 4 , 5 , 3 , 1])))"));
+        assert!(code.contains("Solution :: find_in_mountain_array (FromLeetcodeJson :: from_leetcode_json (& json ! (3)) , & mock)"));
+    }
+
+    #[test]
+    fn test_detect() {
+        let detect = |s: &str| Interactive::detect(&crate::meta::parse_meta(s).unwrap());
+        assert_eq!(
+            detect(
+                r#"{"name": "search", "params": [{"name": "reader", "type": "ArrayReader"}, {"name": "target", "type": "integer"}], "return": {"type": "integer"}}"#
+            ),
+            Some(Interactive::ArrayReader)
+        );
+        // 1533 has an `ArrayReader` with `compareSub` and `length` instead of `get`
+        assert_eq!(
+            detect(
+                r#"{"name": "getIndex", "params": [{"name": "reader", "type": "ArrayReader"}], "return": {"type": "integer"}}"#
+            ),
+            None
+        );
+        assert_eq!(
+            detect(
+                r#"{"name": "guessNumber", "params": [{"name": "n", "type": "integer"}], "return": {"type": "integer"}}"#
+            ),
+            Some(Interactive::Guess)
+        );
+    }
+}
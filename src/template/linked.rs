@@ -0,0 +1,155 @@
+use inflector::Inflector;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::meta::{MetaDataParam, MetaDataType};
+
+/// The Rust type of the generated snippets for lists the judge links from hidden parameters.
+pub const SHARED_LIST_TYPE: &str = "Option<Rc<RefCell<SharedListNode>>>";
+
+/// Linked-list problems whose input is built from hidden parameters: a cycle from `head, pos`,
+/// or two intersecting lists from `intersectVal, listA, listB, skipA, skipB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkedShape {
+    Cycle,
+    Intersection,
+}
+
+impl LinkedShape {
+    pub fn detect(params: &[MetaDataParam]) -> Option<Self> {
+        let has = |name: &str| params.iter().any(|p| p.name == name);
+        if has("intersectVal") && has("skipA") && has("skipB") {
+            Some(LinkedShape::Intersection)
+        } else if has("pos") && params.iter().any(|p| p.r#type == MetaDataType::ListNode) {
+            Some(LinkedShape::Cycle)
+        } else {
+            None
+        }
+    }
+
+    /// The parameters the solution actually receives.
+    fn visible_params(&self) -> &'static [&'static str] {
+        match self {
+            LinkedShape::Cycle => &["head"],
+            LinkedShape::Intersection => &["headA", "headB"],
+        }
+    }
+}
+
+/// A Rust snippet taking shared list nodes, `None` when the return type is unknown.
+pub fn rust_snippet(shape: LinkedShape, name: &str, ret: &MetaDataType) -> Option<String> {
+    let params = shape
+        .visible_params()
+        .iter()
+        .map(|p| format!("{}: {SHARED_LIST_TYPE}", p.to_snake_case()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let ret = match ret {
+        MetaDataType::ListNode => SHARED_LIST_TYPE.to_owned(),
+        t if t.is_known() => t.rust_type(),
+        _ => return None,
+    };
+    Some(format!(
+        "impl Solution {{\n    pub fn {}({params}) -> {ret} {{\n\n    }}\n}}",
+        name.to_snake_case()
+    ))
+}
+
+/// A test over the testcases file, checking the answer against the node the hidden
+/// parameters point at.
+pub fn generate_test_code(
+    shape: LinkedShape,
+    name: &str,
+    params: &[MetaDataParam],
+    ret: &MetaDataType,
+    path: &str,
+) -> TokenStream {
+    let input = |name: &str| {
+        let i = params.iter().position(|p| p.name == name).unwrap_or(0);
+        let i = Literal::usize_unsuffixed(i);
+        quote!(FromLeetcodeJson::from_leetcode_json(&case.inputs[#i]))
+    };
+    let method = format_ident!("{}", name.to_snake_case());
+    let test_name = format_ident!("test_{}", name.to_snake_case());
+    let params_len = Literal::usize_unsuffixed(params.len());
+    let returns_node = ret == &MetaDataType::ListNode;
+
+    let body = match shape {
+        LinkedShape::Cycle => {
+            let head = params
+                .iter()
+                .find(|p| p.r#type == MetaDataType::ListNode)
+                .map_or("head", |p| p.name.as_str());
+            let (values, pos) = (input(head), input("pos"));
+            let check = if returns_node {
+                quote! {
+                    assert_eq!(
+                        shared_list_index(&head, &res),
+                        usize::try_from(pos).ok(),
+                        "case {}: pos = {}", case.index, pos
+                    );
+                }
+            } else {
+                quote!(assert_eq!(res, pos >= 0, "case {}: pos = {}", case.index, pos);)
+            };
+            quote! {
+                let pos: i32 = #pos;
+                let head = build_cycle_list(#values, pos);
+                let arg = head.clone();
+                let res = case.measure(|| Solution::#method(arg));
+                #check
+            }
+        }
+        LinkedShape::Intersection => {
+            let args = ["intersectVal", "listA", "listB", "skipA", "skipB"].map(input);
+            let check = if returns_node {
+                quote!(assert!(same_node(&res, &expected), "case {}: {:?}", case.index, case.inputs);)
+            } else {
+                quote!(assert_eq!(res, expected.is_some(), "case {}: {:?}", case.index, case.inputs);)
+            };
+            quote! {
+                let (head_a, head_b, expected) = build_intersection_lists(#(#args),*);
+                let res = case.measure(|| Solution::#method(head_a, head_b));
+                #check
+            }
+        }
+    };
+    quote! {
+        #[test]
+        pub fn #test_name() {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), #path);
+            for case in load_testcases(path, #params_len) {
+                #body
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linked_shape() {
+        let meta: crate::meta::MetaData = serde_json::from_str(r#"{"name":"getIntersectionNode","params":[{"name":"intersectVal","type":"integer"},{"name":"listA","type":"ListNode"},{"name":"listB","type":"ListNode"},{"name":"skipA","type":"integer"},{"name":"skipB","type":"integer"}],"return":{"type":"ListNode"}}"#).unwrap();
+        let params = match meta {
+            crate::meta::MetaData::Base { params, .. } => params,
+            _ => unreachable!(),
+        };
+        let shape = LinkedShape::detect(&params).unwrap();
+        assert_eq!(shape, LinkedShape::Intersection);
+        assert_eq!(
+            rust_snippet(shape, "getIntersectionNode", &MetaDataType::ListNode).unwrap(),
+            "impl Solution {\n    pub fn get_intersection_node(head_a: Option<Rc<RefCell<SharedListNode>>>, head_b: Option<Rc<RefCell<SharedListNode>>>) -> Option<Rc<RefCell<SharedListNode>>> {\n\n    }\n}"
+        );
+        let code = generate_test_code(
+            shape,
+            "getIntersectionNode",
+            &params,
+            &MetaDataType::ListNode,
+            "/testcases/a.txt",
+        )
+        .to_string();
+        assert!(code.contains("build_intersection_lists (FromLeetcodeJson :: from_leetcode_json (& case . inputs [0]) , FromLeetcodeJson :: from_leetcode_json (& case . inputs [1])"));
+    }
+}
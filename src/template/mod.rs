@@ -1,4 +1,10 @@
+mod interactive;
+mod linked;
+pub mod render;
+mod stress;
 pub mod w;
 
 pub const START_LINE: &str = "// ====== Begin Rust ======";
 pub const END_LINE: &str = "// ====== End Rust ======";
+/// Put on the `impl` block of design problems in generated files, removed by `submit`.
+pub const TEST_OBJECT_ATTR: &str = "#[test_object]";
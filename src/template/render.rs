@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_std::fs;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+pub const TEMPLATE_ENV: &str = "LEETCODE_TEMPLATE";
+
+pub const DEFAULT_TEMPLATE: &str = include_str!("default.tpl");
+
+/// Candidate template files, in lookup order:
+/// `$LEETCODE_TEMPLATE`, `<project>/.leetcode/template.rs`, `<config dir>/leetcode-tool/template.rs`.
+fn template_paths(project_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Ok(p) = std::env::var(TEMPLATE_ENV) {
+        paths.push(PathBuf::from(p));
+    }
+    paths.push(project_dir.join(".leetcode/template.rs"));
+
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| Path::new(&home).join(".config")));
+    if let Ok(dir) = config_dir {
+        paths.push(dir.join("leetcode-tool/template.rs"));
+    }
+    paths
+}
+
+pub async fn load_template(project_dir: &Path) -> Result<String> {
+    for path in template_paths(project_dir) {
+        if path.exists() {
+            log::info!("use template {}", path.display());
+            return Ok(fs::read_to_string(path).await?);
+        }
+    }
+    Ok(DEFAULT_TEMPLATE.to_owned())
+}
+
+/// Replace every `{{ name }}` in `template`, leaving unknown variables untouched.
+pub fn render(template: &str, vars: &[(&str, String)]) -> String {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"\{\{\s*([\w]+)\s*\}\}").unwrap();
+    }
+    RE.replace_all(template, |caps: &Captures| {
+        let name = &caps[1];
+        match vars.iter().find(|(k, _)| *k == name) {
+            Some((_, v)) => v.clone(),
+            None => {
+                log::warn!("unknown template variable `{name}`");
+                caps[0].to_owned()
+            }
+        }
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let vars = [("title", "两数之和".to_owned()), ("id", "1".to_owned())];
+        let s = render("// {{id}}. {{ title }} {{unknown}}", &vars);
+        assert_eq!(s, "// 1. 两数之和 {{unknown}}");
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::Result;
+use inflector::Inflector;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::meta::{MetaDataParam, MetaDataType};
+
+use super::w::binding_ident;
+
+/// Whether random inputs of the type can be generated.
+fn is_generated(t: &MetaDataType) -> bool {
+    match t {
+        MetaDataType::List(t) => is_generated(t),
+        MetaDataType::Integer
+        | MetaDataType::Long
+        | MetaDataType::Double
+        | MetaDataType::Bool
+        | MetaDataType::Character
+        | MetaDataType::String
+        | MetaDataType::ListNode
+        | MetaDataType::TreeNode => true,
+        _ => false,
+    }
+}
+
+/// A `brute_force` stub with the solution's signature, an ignored test comparing the two on
+/// random inputs within `constraints`, and an ignored test timing the solution on maximal
+/// inputs, reading the declared complexity from the solution file `source`, and an ignored
+/// benchmark of the `VARIANTS` on the examples in `path` and maximal inputs. `None` when an
+/// input can't be generated.
+pub fn generate_stress_code(
+    name: &str,
+    params: &[MetaDataParam],
+    ret: &MetaDataType,
+    output_param: Option<usize>,
+    constraints: &[String],
+    source: &str,
+    path: &str,
+) -> Result<Option<TokenStream>> {
+    if !params.iter().all(|p| is_generated(&p.r#type)) {
+        return Ok(None);
+    }
+    let mut bindings = vec![];
+    let mut args = vec![];
+    let mut signature = vec![];
+    // every argument converted before the call, for timing
+    let mut perf_bindings = vec![];
+    let mut perf_args = vec![];
+    for (i, param) in params.iter().enumerate() {
+        let index = Literal::usize_unsuffixed(i);
+        let value = quote!(FromLeetcodeJson::from_leetcode_json(&inputs[#index]));
+        let ident = binding_ident(&param.name, i);
+        let ty: syn::Type = syn::parse_str(&param.r#type.rust_type())?;
+        if output_param == Some(i) {
+            bindings.push(quote!(let mut #ident: #ty = #value;));
+            args.push(quote!(&mut #ident));
+            signature.push(quote!(#ident: &mut #ty));
+            perf_bindings.push(quote!(let mut #ident: #ty = #value;));
+            perf_args.push(quote!(&mut #ident));
+        } else {
+            perf_bindings.push(quote!(let #ident: #ty = #value;));
+            args.push(value);
+            signature.push(quote!(#ident: #ty));
+            perf_args.push(quote!(#ident));
+        }
+    }
+    let (ret_type, output) = match output_param {
+        Some(i) => (quote!(), Some(binding_ident(&params[i].name, i))),
+        None => {
+            let ty: syn::Type = syn::parse_str(&ret.rust_type())?;
+            (quote!(-> #ty), None)
+        }
+    };
+    let run = |call: TokenStream| match &output {
+        Some(output) => quote! {
+            |inputs: &[Value]| {
+                #(#bindings)*
+                #call;
+                #output.to_leetcode_json()
+            }
+        },
+        None => quote!(|inputs: &[Value]| #call.to_leetcode_json()),
+    };
+
+    let method = format_ident!("{}", name.to_snake_case());
+    let test_name = format_ident!("stress_{}", name.to_snake_case());
+    let perf_name = format_ident!("perf_{}", name.to_snake_case());
+    let bench_name = format_ident!("bench_{}", name.to_snake_case());
+    let doc = format!(" Slow but obviously correct, `{test_name}` checks the solution against it.");
+    let solve = run(quote!(Solution::#method(#(#args),*)));
+    let timed = |call: TokenStream| match &output {
+        Some(output) => quote!({
+            #call;
+            #output.to_leetcode_json()
+        }),
+        None => quote!(#call.to_leetcode_json()),
+    };
+    let call = timed(quote!(Solution::#method(#(#perf_args),*)));
+    let variant_call = timed(quote!(solve(#(#perf_args),*)));
+    let brute = run(quote!(brute_force(#(#args),*)));
+    let types = params.iter().map(|p| {
+        let (name, ty) = (&p.name, p.r#type.to_string());
+        quote!((#name, #ty))
+    });
+    Ok(Some(quote! {
+        /// Parameters and constraints of the generated inputs, edit them to generate others.
+        #[cfg(test)]
+        const PARAMS: &[(&str, &str)] = &[#(#types),*];
+        #[cfg(test)]
+        const CONSTRAINTS: &[&str] = &[#(#constraints),*];
+
+        #[doc = #doc]
+        #[cfg(test)]
+        #[allow(unused_variables)]
+        fn brute_force(#(#signature),*) #ret_type {
+            todo!("brute force for the stress test")
+        }
+
+        #[test]
+        #[ignore = "stress test, panics until `brute_force` is written, then run `cargo test stress -- --ignored --nocapture`"]
+        pub fn #test_name() {
+            stress(PARAMS, CONSTRAINTS, #solve, #brute, <Solution as Checker>::check);
+        }
+
+        #[test]
+        #[ignore = "perf test, run `leetcode-tool perf <slug>`"]
+        pub fn #perf_name() {
+            perf(PARAMS, CONSTRAINTS, include_str!(#source), |inputs: &[Value]| {
+                #(#perf_bindings)*
+                move || #call
+            });
+        }
+
+        #[test]
+        #[ignore = "benchmark, run `leetcode-tool perf <slug>`"]
+        pub fn #bench_name() {
+            let variants = VARIANTS
+                .iter()
+                .map(|&(name, solve)| {
+                    (name, move |inputs: &[Value]| {
+                        #(#perf_bindings)*
+                        move || #variant_call
+                    })
+                })
+                .collect::<Vec<_>>();
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), #path);
+            bench(path, PARAMS, CONSTRAINTS, &variants);
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_stress_code() {
+        let meta: crate::meta::MetaData = serde_json::from_str(r#"{"name":"rotate","params":[{"name":"nums","type":"integer[]"},{"name":"k","type":"integer"}],"return":{"type":"void"}}"#).unwrap();
+        let params = match meta {
+            crate::meta::MetaData::Base { params, .. } => params,
+            _ => unreachable!(),
+        };
+        let lines = vec!["1 <= nums.length <= 10^5".to_owned()];
+        let code = generate_stress_code(
+            "rotate",
+            &params,
+            &MetaDataType::Void,
+            Some(0),
+            &lines,
+            "rotate_array.rs",
+            "/testcases/rotate-array.txt",
+        )
+        .unwrap()
+        .unwrap()
+        .to_string();
+        assert!(code.contains("fn brute_force (nums : & mut Vec < i32 > , k : i32)"));
+        assert!(code.contains("# [cfg (test)] const PARAMS : & [(& str , & str)] = & [(\"nums\" , \"integer[]\") , (\"k\" , \"integer\")] ;"));
+        assert!(code.contains(
+            "# [cfg (test)] const CONSTRAINTS : & [& str] = & [\"1 <= nums.length <= 10^5\"] ;"
+        ));
+        assert!(code.contains("perf (PARAMS , CONSTRAINTS , include_str ! (\"rotate_array.rs\") , | inputs : & [Value] | { let mut nums : Vec < i32 > = FromLeetcodeJson :: from_leetcode_json (& inputs [0]) ; let k : i32 = FromLeetcodeJson :: from_leetcode_json (& inputs [1]) ; move || { Solution :: rotate (& mut nums , k) ; nums . to_leetcode_json () } })"));
+        assert!(code.contains("move || { solve (& mut nums , k) ; nums . to_leetcode_json () }"));
+    }
+}
@@ -2,56 +2,93 @@ use std::path::PathBuf;
 
 use anyhow::{bail, Result};
 use async_std::{
-    fs::{File, OpenOptions},
+    fs::{create_dir_all, File},
     io::BufWriter,
     io::WriteExt,
-    process::Command,
 };
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
 
 use crate::{
-    domain::{CodeSnippet, Question},
-    guest::guest_output,
-    meta::{MetaData, MetaDataMethod, MetaDataType},
-    testcase::{parse_class_test_cases2, parse_test_cases},
+    constraint::constraint_lines,
+    domain::Question,
+    guest::{guest_any_order, parse_examples, Example},
+    layout::{register_module, Layout},
+    meta::{node_kind, MetaData, MetaDataParam, MetaDataType},
+    testcase::{
+        format_testcases, parse_class_test_cases2, parse_test_cases, testcases_path, TESTCASES_DIR,
+    },
 };
 use inflector::Inflector;
 
-use super::{END_LINE, START_LINE};
+use super::{
+    interactive::{self, Interactive},
+    linked::{self, LinkedShape},
+    render::{load_template, render},
+    stress::generate_stress_code,
+    END_LINE, START_LINE, TEST_OBJECT_ATTR,
+};
 
 struct WriteTemplate<'a> {
     question: &'a Question,
-    snippet: &'a CodeSnippet,
+    snippet_code: String,
     test_code: Option<String>,
+    testcases: Option<String>,
     // import_code: Vec<String>,
     title: String,
 }
 
 impl<'a> WriteTemplate<'a> {
     fn new(question: &'a Question) -> Result<Self, anyhow::Error> {
-        let snippet = match question.code_snippets.iter().find(|c| c.lang == "Rust") {
-            Some(snippet) => snippet,
-            None => bail!("Fail to get Rust code Snippet"),
+        let snippet_code = match question.code_snippets.iter().find(|c| c.lang == "Rust") {
+            Some(snippet) => snippet.code.clone(),
+            // problems using LeetCode's `Node` types have no Rust snippet
+            None => match rust_snippet(&parse_meta(question)?) {
+                Some(code) => {
+                    log::warn!("no Rust snippet, generate one from metaData");
+                    code
+                }
+                None => bail!("Fail to get Rust code Snippet"),
+            },
         };
 
         let title = question.title_slug.replace('-', "_");
         Ok(Self {
             question,
-            snippet,
+            snippet_code,
             test_code: None,
+            testcases: None,
             // import_code: vec![],
             title,
         })
     }
 
     fn generate_test_code(&mut self) -> Result<bool, anyhow::Error> {
-        let meta: MetaData = serde_json::from_str(&self.question.meta_data)?;
+        let meta = parse_meta(self.question)?;
 
-        let v = match meta {
+        if let Some(kind) = Interactive::detect(&meta) {
+            let tokens =
+                interactive::generate_test_code(kind, &self.snippet_code, &self.examples())?;
+            self.test_code = Some(pretty_print(tokens)?);
+            return Ok(false);
+        }
+
+        let (tokens, is_class) = match meta {
             MetaData::Base {
                 name,
                 params,
                 r#return,
             } => {
+                if let Some(t) = params
+                    .iter()
+                    .map(|p| &p.r#type)
+                    .chain(std::iter::once(&r#return.r#type))
+                    .find(|t| !t.is_known())
+                {
+                    log::warn!("unsupported type `{t}`, skip generating tests");
+                    return Ok(false);
+                }
+
                 let test_cases = {
                     let test_cases_str = if let Some(s) = self.question.example_testcases.as_ref() {
                         s
@@ -61,137 +98,230 @@ impl<'a> WriteTemplate<'a> {
                     parse_test_cases(test_cases_str)?
                 };
 
-                let mut output_iter =
-                    guest_output(&self.question.translated_content).filter_map(|output| {
-                        let o = match r#return.r#type {
-                            MetaDataType::Integer => {
-                                let num = output.parse().ok()?;
-                                serde_json::Value::Number(num)
-                            }
+                let param_names = params.iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+                let cases = example_cases(
+                    &self.examples(),
+                    &param_names,
+                    into_test_cases_iter(test_cases, params.len()).collect(),
+                );
+                self.testcases = Some(format_testcases(&param_names, &cases));
+                let path = format!("/{}/{}.txt", TESTCASES_DIR, self.question.title_slug);
+
+                if let Some(shape) = LinkedShape::detect(&params)
+                    .filter(|_| self.snippet_code.contains(linked::SHARED_LIST_TYPE))
+                {
+                    let tokens =
+                        linked::generate_test_code(shape, &name, &params, &r#return.r#type, &path);
+                    self.test_code = Some(pretty_print(tokens)?);
+                    return Ok(false);
+                }
 
-                            _ => serde_json::from_str(&output).ok()?,
-                        };
-                        json_value_to_rust(&o, &r#return.r#type).ok()
-                    });
                 let method_name = name.to_snake_case();
+                let method = format_ident!("{}", method_name);
+                let test_name = format_ident!("test_{}", method_name);
+                let params_len = Literal::usize_unsuffixed(params.len());
+
+                // in-place problems return nothing and compare the mutated parameter
+                let output_param = match (&r#return.r#type, &r#return.output) {
+                    (MetaDataType::Void, Some(output)) if output.paramindex < params.len() => {
+                        Some(output.paramindex)
+                    }
+                    _ => None,
+                };
+                let mut bindings = vec![];
+                let mut args = vec![];
+                // the arguments are converted before `measure`, which counts only the call
+                for (i, param) in params.iter().enumerate() {
+                    let index = Literal::usize_unsuffixed(i);
+                    let value = quote!(FromLeetcodeJson::from_leetcode_json(&case.inputs[#index]));
+                    let ident = binding_ident(&param.name, i);
+                    let ty: syn::Type = syn::parse_str(&param.r#type.rust_type())?;
+                    if output_param == Some(i) {
+                        bindings.push(quote!(let mut #ident: #ty = #value;));
+                        args.push(quote!(&mut #ident));
+                    } else {
+                        bindings.push(quote!(let #ident: #ty = #value;));
+                        args.push(quote!(#ident));
+                    }
+                }
+                let call = quote!(solve(#(#args),*));
+                let variants = variants_const(&params, &r#return.r#type, output_param, &method)?;
+
+                let (run, actual, actual_type) = match output_param {
+                    Some(i) => (
+                        quote!(#(#bindings)* case.measure(|| #call);),
+                        binding_ident(&params[i].name, i),
+                        &params[i].r#type,
+                    ),
+                    None => (
+                        quote!(#(#bindings)* let res = case.measure(|| #call);),
+                        format_ident!("res"),
+                        &r#return.r#type,
+                    ),
+                };
+                let any_order = guest_any_order(&self.question.translated_content)
+                    || guest_any_order(&self.question.content);
+                let (checker, check) = if r#return.is_manual() {
+                    (quote!(), quote!(case.print(#actual)))
+                } else {
+                    let compare = if actual_type.is_float() {
+                        Some(quote!(approx_eq))
+                    } else if any_order {
+                        Some(quote!(unordered_eq))
+                    } else {
+                        None
+                    };
+                    let body = compare.map(|compare| {
+                        quote! {
+                            fn check(_inputs: &[Value], expected: &Value, actual: &Value) -> bool {
+                                #compare(actual, expected)
+                            }
+                        }
+                    });
+                    (
+                        quote! {
+                            /// Override `check` to accept every valid answer.
+                            impl Checker for Solution {
+                                #body
+                            }
+                        },
+                        quote!(case.assert_by(#actual, <Solution as Checker>::check)),
+                    )
+                };
+                let attrs = if r#return.is_manual() {
+                    quote! {
+                        /// Manual check: the judge accepts more than one answer, compare the printed results by hand.
+                        #[test]
+                        #[ignore = "manual check, run with `cargo test -- --ignored --nocapture`"]
+                    }
+                } else {
+                    quote!(#[test])
+                };
 
-                let test_cases = into_test_cases_iter(test_cases, params.len())
-                    // .enumerate()
-                    .map(|test_case| {
-                        // if r#return.r#type == MetaDataType::Void {
-                        //     format!(r"
-                        //         let mut param{i} =
-                        //         let res{i}
-                        //     ")
-                        // }
-                        let params_str =
-                            format_params(test_case.iter(), params.iter().map(|p| &p.r#type));
-                        let expects = output_iter.next().unwrap_or_default();
-
-                        format!("assert_eq!(Solution::{method_name}({params_str}), {expects});")
-                    })
-                    .collect::<Vec<String>>()
-                    .join("\n");
-
-                let test_code = format!(
-                    r"
-                #[test]
-                    pub fn test_{method_name}() {{
-                        {test_cases}
-                }}
-                "
-                );
-
-                self.test_code = Some(test_code);
+                let tokens = if actual_type == &MetaDataType::Void {
+                    log::warn!("`{name}` returns nothing, generated test only calls it");
+                    quote! {
+                        #variants
+                        #attrs
+                        pub fn #test_name() {
+                            let path = concat!(env!("CARGO_MANIFEST_DIR"), #path);
+                            for (variant, solve) in VARIANTS {
+                                println!("variant `{variant}`");
+                                for case in load_testcases(path, #params_len) {
+                                    #(#bindings)*
+                                    case.measure(|| #call);
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    let stress = if r#return.is_manual() {
+                        None
+                    } else {
+                        generate_stress_code(
+                            &name,
+                            &params,
+                            &r#return.r#type,
+                            output_param,
+                            &constraint_lines(self.statement()),
+                            &format!("{}.rs", self.title),
+                            &path,
+                        )?
+                    };
+                    quote! {
+                        #variants
+                        #checker
+                        #attrs
+                        pub fn #test_name() {
+                            let path = concat!(env!("CARGO_MANIFEST_DIR"), #path);
+                            for (variant, solve) in VARIANTS {
+                                println!("variant `{variant}`");
+                                for case in load_testcases(path, #params_len) {
+                                    #run
+                                    #check;
+                                }
+                            }
+                        }
+                        #stress
+                    }
+                };
 
-                false
+                (tokens, false)
             }
             MetaData::Class {
                 classname,
                 constructor,
-                methods,
+                methods: _,
                 r#return: _,
             } => {
-                // let struct_name = parse_struct_name(&self.snippet.code).unwrap_or("UnknowStruct");
-
-                // self.import_code
-                //     .push("use leetcode_tool::{TestObject, assert_object};".into());
-                // self.import_code.push("use serde_json::Value;".into());
-
-                let method_code = methods.iter().map(
-                    |MetaDataMethod {
-                         name,
-                         params,
-                         r#return,
-                     }| {
-
-                        let param_lines = params.iter().enumerate().map(|(i, param)| {
-                            let s = json_to_rust(&format!("params[{i}]"), &param.r#type);
-
-                            format!("let p{i} = {s};")
-                        }).collect::<Vec<String>>();
-                        let res = rust_to_json_value("res", &r#return.r#type);
-
-                        let ps_code = param_lines.join("\n");
-                        let ps_code2 = param_lines.iter().enumerate().map(|(i, _)| format!("p{i}")).collect::<Vec<String>>().join(",");
-
-                        let method_name = name.to_snake_case();
-                        let body = if let Some(res) = res {
-                            format!("{ps_code}\nlet res = self.{method_name}({ps_code2});\nreturn Some({res})")
-                        } else {
-                            format!("{ps_code}\nself.{method_name}({ps_code2});")
-                        };
-
-                        format!("\"{name}\" => {{ {body} }}")
-                     },
-                ).collect::<Vec<String>>().join("\n");
-
-                let mut output_iter = guest_output(&self.question.translated_content);
-
-                let (methods_json, params_json) = get_class_output(self.question)?;
-
-                let param_value = into_array(params_json).unwrap();
-                let constructor_param = {
-                    format_params(
-                        param_value[0].as_array().unwrap().iter(),
-                        constructor.params.iter().map(|p| &p.r#type),
-                    )
-                };
+                match add_test_object_attr(&self.snippet_code, &classname) {
+                    Some(code) => self.snippet_code = code,
+                    None => log::warn!("`impl {classname}` not found in the snippet"),
+                }
 
-                let excepts_json = match output_iter.next() {
-                    Some(output) => output.to_string(),
-                    None => {
-                        let params_len = param_value.len();
-                        format!("[{}]", vec!["null"; params_len].join(","))
+                let class = format_ident!("{}", classname);
+                let test_name = classname.to_snake_case();
+                let examples = self.examples();
+                let class_cases = class_cases(self.question);
+                let many = class_cases.len() > 1;
+                let positional = examples.len() == class_cases.len();
+                let mut tests = vec![];
+                for (i, (methods_json, params_json)) in class_cases.into_iter().enumerate() {
+                    let param_value = into_array(params_json).unwrap_or_default();
+                    let constructor_param = match param_value.first().and_then(|p| p.as_array()) {
+                        Some(values) => format_params(
+                            values.iter(),
+                            constructor.params.iter().map(|p| &p.r#type),
+                        ),
+                        None => vec![],
+                    };
+                    let output = class_output(&examples, methods_json, params_json, i, positional);
+                    if output.is_none() {
+                        log::warn!("no output found for example {}, only print results", i + 1);
                     }
-                };
 
-                let classname2 = classname.to_snake_case();
-                let test_code = format!(
-                    r#"
-            impl TestObject for {classname} {{
-                fn call(&mut self, method: &str, params: &[Value]) -> Option<Value> {{
-                    match method {{
-                        {method_code}
-                        _ => {{}},
-                    }}
-                    None
-                }}
-            }}
-
-            #[test]
-            pub fn test_{classname2}() {{
-                assert_object({classname}::new({constructor_param}), json!({methods_json}), json!({params_json}), json!({excepts_json}));
-            }}
-            "#,
-                );
+                    let test_name = if many {
+                        format_ident!("test_{}_{}", test_name, i + 1)
+                    } else {
+                        format_ident!("test_{}", test_name)
+                    };
+                    let methods_json = json_tokens(methods_json)?;
+                    let params_json = json_tokens(params_json)?;
+                    let excepts_json = json_value_tokens(&output.unwrap_or_default());
+                    tests.push(quote! {
+                        #[test]
+                        pub fn #test_name() {
+                            assert_object(
+                                #class::new(#(#constructor_param),*),
+                                #methods_json,
+                                #params_json,
+                                json!(#excepts_json),
+                            );
+                        }
+                    });
+                }
 
-                self.test_code = Some(test_code);
-                true
+                (quote!(#(#tests)*), true)
             }
         };
 
-        Ok(v)
+        self.test_code = Some(pretty_print(tokens)?);
+        Ok(is_class)
+    }
+
+    /// The statement as HTML, Chinese when there is a translation.
+    fn statement(&self) -> &str {
+        statement(self.question)
+    }
+
+    /// Examples of the Chinese statement, or of the English one when it has none.
+    fn examples(&self) -> Vec<Example> {
+        let examples = parse_examples(&self.question.translated_content);
+        if examples.is_empty() {
+            parse_examples(&self.question.content)
+        } else {
+            examples
+        }
     }
 
     fn get_doc_code(&self) -> String {
@@ -199,119 +329,406 @@ impl<'a> WriteTemplate<'a> {
             title_slug,
             translated_title,
             difficulty,
-            translated_content,
             ..
         } = self.question;
 
-        let md_lines = html2md::parse_html(translated_content)
-            .split('\n')
-            .map(|line| format!("/// {line}"))
-            .collect::<Vec<String>>()
-            .join("\n");
+        let md_lines = doc_lines(&self.get_description());
         format!(
-            r"
-        /// # {translated_title}
-        ///
-        {md_lines}
-        ///
-        /// src: https://leetcode.cn/problems/{title_slug}/
-        ///
-        /// difficulty: `{difficulty}`
-    "
+            "/// # {translated_title}
+///
+{md_lines}
+///
+/// src: https://leetcode.cn/problems/{title_slug}/
+///
+/// difficulty: `{difficulty}`"
         )
     }
 
-    async fn write_to(&mut self, project_dir: PathBuf) -> Result<PathBuf, anyhow::Error> {
+    fn get_description(&self) -> String {
+        html2md::parse_html(&self.question.translated_content)
+    }
+
+    fn get_hints(&self) -> String {
+        self.question
+            .hints
+            .iter()
+            .map(|hint| format!("- {}", html2md::parse_html(hint)))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn template_vars(&mut self, is_class: bool) -> Vec<(&'static str, String)> {
+        let question = self.question;
+        let tags = question
+            .topic_tags
+            .iter()
+            .map(|t| t.translated_name.as_ref().unwrap_or(&t.name).as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        let description = self.get_description();
+        let hints = self.get_hints();
+
+        vec![
+            ("title", question.translated_title.clone()),
+            ("title_en", question.title.clone()),
+            ("slug", question.title_slug.clone()),
+            ("module", self.title.clone()),
+            (
+                "id",
+                question
+                    .question_frontend_id
+                    .clone()
+                    .unwrap_or_else(|| question.question_id.clone()),
+            ),
+            ("difficulty", question.difficulty.clone()),
+            ("tags", tags),
+            ("doc", self.get_doc_code()),
+            ("description_doc", doc_lines(&description)),
+            ("description", description),
+            ("hints_doc", doc_lines(&hints)),
+            ("hints", hints),
+            (
+                "struct",
+                if is_class { "" } else { "pub struct Solution;" }.to_owned(),
+            ),
+            ("start_line", START_LINE.to_owned()),
+            ("snippet", self.snippet_code.clone()),
+            ("end_line", END_LINE.to_owned()),
+            ("test_code", self.test_code.take().unwrap_or_default()),
+        ]
+    }
+
+    async fn write_to(
+        &mut self,
+        project_dir: PathBuf,
+        layout: Layout,
+    ) -> Result<PathBuf, anyhow::Error> {
         let is_class = self.generate_test_code()?;
-        let file_path = project_dir.join(format!("src/{}.rs", self.title));
+        let file_path = layout.solution_path(&project_dir, &self.title, &self.question.difficulty);
+        if let Some(parent) = file_path.parent() {
+            create_dir_all(parent).await?;
+        }
+
+        let template = load_template(&project_dir).await?;
+        let code = render(&template, &self.template_vars(is_class));
+        if !code.contains(START_LINE) || !code.contains(END_LINE) || !code.contains("/// src:") {
+            log::warn!("template lacks `{{{{doc}}}}`, `{{{{start_line}}}}` or `{{{{end_line}}}}`, `submit` will not find the code");
+        }
+
         let file = File::create(&file_path).await?;
         let mut buf_writer = BufWriter::new(file);
+        buf_writer.write_all(code.as_bytes()).await?;
+        buf_writer.flush().await?;
 
-        let import_code = "use leetcode_tool::prelude::*;";
-        let doc_code = self.get_doc_code();
-        let struct_code = if is_class { "" } else { "pub struct Solution;" };
-        let test_code = self.test_code.take().unwrap_or_default();
-        let snippet_code = &self.snippet.code;
-
-        buf_writer
-            .write(
-                format!("{import_code}\n{doc_code}\n{struct_code}\n{START_LINE}\n{snippet_code}\n{END_LINE}\n{test_code}")
-                .as_bytes(),
-            )
-            .await?;
+        register_module(&project_dir, &file_path).await?;
 
-        buf_writer.flush().await?;
+        if let Some(testcases) = self.testcases.take() {
+            let path = testcases_path(&project_dir, &self.question.title_slug);
+            if path.exists() {
+                log::info!("keep existing testcases {}", path.display());
+            } else {
+                create_dir_all(path.parent().unwrap()).await?;
+                async_std::fs::write(&path, testcases).await?;
+            }
+        }
 
-        let mut lib_file = OpenOptions::new()
-            .write(true)
-            .append(true)
-            .open(project_dir.join("src/lib.rs"))
-            .await?;
-        lib_file
-            .write(format!("\npub mod {};", self.title).as_bytes())
-            .await?;
-        lib_file.flush().await?;
-
-        if let Err(err) = cargo_fmt(project_dir).await {
-            log::warn!("`cargo fmt` process failed to execute: {:?}", err);
-        };
         Ok(file_path)
     }
 }
 
-pub async fn write_template(question: &Question, project_dir: PathBuf) -> Result<PathBuf> {
+/// The metaData of the question, `Node` types resolved from the other languages' snippets and
+/// constraints attached from the statement.
+fn parse_meta(question: &Question) -> Result<MetaData> {
+    let mut meta: MetaData = serde_json::from_str(&question.meta_data)?;
+    let code = question
+        .code_snippets
+        .iter()
+        .filter(|c| c.lang != "Rust")
+        .map(|c| c.code.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if let Some(kind) = node_kind(&code) {
+        meta.resolve_nodes(kind);
+    }
+    meta.attach_constraints(statement(question));
+    Ok(meta)
+}
+
+fn statement(question: &Question) -> &str {
+    if question.translated_content.is_empty() {
+        &question.content
+    } else {
+        &question.translated_content
+    }
+}
+
+fn rust_signature(
+    name: &str,
+    receiver: Option<&str>,
+    params: &[MetaDataParam],
+    ret: &MetaDataType,
+) -> Option<String> {
+    let params = receiver
+        .map(str::to_owned)
+        .into_iter()
+        .chain(
+            params
+                .iter()
+                .map(|p| format!("{}: {}", p.name.to_snake_case(), p.r#type.rust_type())),
+        )
+        .collect::<Vec<_>>()
+        .join(", ");
+    let ret = match ret {
+        MetaDataType::Void => String::new(),
+        t if t.is_known() => format!(" -> {}", t.rust_type()),
+        _ => return None,
+    };
+    Some(format!(
+        "fn {}({params}){ret} {{\n\n    }}",
+        name.to_snake_case()
+    ))
+}
+
+/// The approaches to test and benchmark, the submitted one first.
+fn variants_const(
+    params: &[MetaDataParam],
+    ret: &MetaDataType,
+    output_param: Option<usize>,
+    method: &Ident,
+) -> Result<TokenStream> {
+    let mut types = vec![];
+    for (i, param) in params.iter().enumerate() {
+        let ty: syn::Type = syn::parse_str(&param.r#type.rust_type())?;
+        types.push(if output_param == Some(i) {
+            quote!(&mut #ty)
+        } else {
+            quote!(#ty)
+        });
+    }
+    let ret = match ret {
+        MetaDataType::Void => quote!(),
+        t => {
+            let ty: syn::Type = syn::parse_str(&t.rust_type())?;
+            quote!(-> #ty)
+        }
+    };
+    let more = format!(
+        " Add others as `(\"Solution2\", Solution2::{method})` or `(\"dp\", Solution::{method}_dp)`,"
+    );
+    Ok(quote! {
+        /// Approaches run on every example and benchmarked, the submitted one first.
+        #[doc = #more]
+        /// between the Begin/End lines to submit them with `submit --variant`.
+        #[cfg(test)]
+        const VARIANTS: &[(&str, fn(#(#types),*) #ret)] = &[("Solution", Solution::#method)];
+    })
+}
+
+/// A Rust snippet in LeetCode's style built from metaData, `None` when a type is unknown.
+fn rust_snippet(meta: &MetaData) -> Option<String> {
+    match meta {
+        MetaData::Base {
+            name,
+            params,
+            r#return,
+        } => {
+            if let Some(shape) = LinkedShape::detect(params) {
+                return linked::rust_snippet(shape, name, &r#return.r#type);
+            }
+            if !params.iter().all(|p| p.r#type.is_known()) {
+                return None;
+            }
+            let signature = rust_signature(name, None, params, &r#return.r#type)?;
+            Some(format!("impl Solution {{\n    pub {signature}\n}}"))
+        }
+        MetaData::Class {
+            classname,
+            constructor,
+            methods,
+            ..
+        } => {
+            let params = constructor
+                .params
+                .iter()
+                .chain(methods.iter().flat_map(|m| m.params.iter()));
+            if !params.into_iter().all(|p| p.r#type.is_known()) {
+                return None;
+            }
+            let constructor = format!(
+                "fn new({}) -> Self {{\n\n    }}",
+                constructor
+                    .params
+                    .iter()
+                    .map(|p| format!("{}: {}", p.name.to_snake_case(), p.r#type.rust_type()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let methods = methods
+                .iter()
+                .map(|m| rust_signature(&m.name, Some("&mut self"), &m.params, &m.r#return.r#type))
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!(
+                "struct {classname} {{\n\n}}\n\nimpl {classname} {{\n\n    {}\n}}",
+                std::iter::once(constructor)
+                    .chain(methods)
+                    .collect::<Vec<_>>()
+                    .join("\n\n    ")
+            ))
+        }
+    }
+}
+
+fn doc_lines(s: &str) -> String {
+    s.split('\n')
+        .map(|line| match line.trim_end() {
+            "" => "///".to_owned(),
+            line => format!("/// {line}"),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub async fn write_template(
+    question: &Question,
+    project_dir: PathBuf,
+    layout: Layout,
+) -> Result<PathBuf> {
     let mut wt = WriteTemplate::new(question)?;
-    let pb = wt.write_to(project_dir).await?;
+    let pb = wt.write_to(project_dir, layout).await?;
     Ok(pb)
 }
 
-async fn cargo_fmt(project_dir: PathBuf) -> Result<std::process::ExitStatus> {
-    let res = Command::new("cargo")
-        .arg("fmt")
-        .current_dir(project_dir)
-        .status()
-        .await?;
-    Ok(res)
+fn pretty_print(tokens: TokenStream) -> Result<String> {
+    let file: syn::File = syn::parse2(tokens)?;
+    let items = file
+        .items
+        .into_iter()
+        .map(|item| {
+            prettyplease::unparse(&syn::File {
+                shebang: None,
+                attrs: vec![],
+                items: vec![item],
+            })
+        })
+        .collect::<Vec<String>>();
+    Ok(items.join("\n"))
 }
 
-fn json_value_to_rust(val: &serde_json::Value, meta_type: &MetaDataType) -> Result<String> {
-    let v = match meta_type {
-        MetaDataType::List(sub_meta_type) => {
-            let array = match val {
-                serde_json::Value::Array(a) => a,
-                _ => {
-                    bail!("json_value_to_rust parse error: {} {:?}", val, meta_type);
-                }
-            };
-            format!(
-                "vec![{}]",
-                array
-                    .iter()
-                    .filter_map(|v| match json_value_to_rust(v, sub_meta_type) {
-                        Ok(o) => Some(o),
-                        Err(err) => {
-                            log::warn!("json_value_to_rust: {:?}", err);
-                            None
-                        }
-                    })
-                    .collect::<Vec<String>>()
-                    .join(",")
-            )
+/// A `json!(...)` expression rebuilding `json` inside the generated code.
+fn json_tokens(json: &str) -> Result<TokenStream> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let value = json_value_tokens(&value);
+    Ok(quote!(json!(#value)))
+}
+
+pub(super) fn json_value_tokens(value: &serde_json::Value) -> TokenStream {
+    match value {
+        serde_json::Value::Null => quote!(null),
+        serde_json::Value::Bool(b) => quote!(#b),
+        serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => int_literal(i),
+            (None, f) => {
+                let lit = Literal::f64_unsuffixed(f.unwrap_or_default());
+                quote!(#lit)
+            }
+        },
+        serde_json::Value::String(s) => {
+            let lit = Literal::string(s);
+            quote!(#lit)
+        }
+        serde_json::Value::Array(a) => {
+            let items = a.iter().map(json_value_tokens);
+            quote!([#(#items),*])
+        }
+        serde_json::Value::Object(o) => {
+            let items = o.iter().map(|(k, v)| {
+                let v = json_value_tokens(v);
+                quote!(#k: #v)
+            });
+            quote!({#(#items),*})
+        }
+    }
+}
+
+/// A variable named after the parameter, or `p{i}` when the name is not a valid identifier.
+pub(super) fn binding_ident(name: &str, i: usize) -> Ident {
+    let name = name.to_snake_case();
+    syn::parse_str::<Ident>(&name).unwrap_or_else(|_| format_ident!("p{}", i))
+}
+
+fn json_value_to_rust(val: &serde_json::Value, meta_type: &MetaDataType) -> Result<TokenStream> {
+    let v = match (meta_type, val) {
+        (MetaDataType::List(sub_meta_type), serde_json::Value::Array(array)) => {
+            let items = array
+                .iter()
+                .filter_map(|v| match json_value_to_rust(v, sub_meta_type) {
+                    Ok(o) => Some(o),
+                    Err(err) => {
+                        log::warn!("json_value_to_rust: {:?}", err);
+                        None
+                    }
+                });
+            quote!(vec![#(#items),*])
+        }
+        (MetaDataType::ListNode, _) => {
+            let lit = Literal::string(&val.to_string());
+            quote!(ListNode::from_jsonstr(#lit))
         }
-        MetaDataType::ListNode => {
-            format!("ListNode::from_jsonstr(\"{}\")", val)
+        (MetaDataType::TreeNode, _) => {
+            let lit = Literal::string(&val.to_string());
+            quote!(TreeNode::from_jsonstr(#lit))
         }
-        MetaDataType::TreeNode => {
-            format!("TreeNode::from_jsonstr(\"{}\")", val)
+        (MetaDataType::Node(Some(kind)), _) => {
+            let node = format_ident!("{}", kind.rust_type());
+            let lit = Literal::string(&val.to_string());
+            quote!(#node::from_jsonstr(#lit))
         }
-        MetaDataType::Character => format!("'{}'", val),
-        MetaDataType::String => format!("{}.to_owned()", val),
-        MetaDataType::Unknow(t) => bail!("Unknow MetaType {}", t),
-        _ => val.to_string(),
+        (MetaDataType::NestedInteger, _) => {
+            let lit = Literal::string(&val.to_string());
+            quote!(NestedInteger::from_jsonstr(#lit))
+        }
+        (MetaDataType::Interval, serde_json::Value::Array(a)) if a.len() == 2 => {
+            let (start, end) = (json_value_tokens(&a[0]), json_value_tokens(&a[1]));
+            quote!(Interval::new(#start, #end))
+        }
+        (MetaDataType::Character, serde_json::Value::String(s)) if s.chars().count() == 1 => {
+            let lit = Literal::character(s.chars().next().unwrap());
+            quote!(#lit)
+        }
+        (MetaDataType::String, serde_json::Value::String(s)) => {
+            let lit = Literal::string(s);
+            quote!(#lit.to_owned())
+        }
+        (MetaDataType::Integer, serde_json::Value::Number(n)) => match n.as_i64() {
+            Some(n) => int_literal(n),
+            None => bail!("json_value_to_rust parse error: {} {:?}", val, meta_type),
+        },
+        (MetaDataType::Long, serde_json::Value::Number(n)) => match n.as_i64() {
+            Some(n) => {
+                let lit = Literal::i64_suffixed(n);
+                quote!(#lit)
+            }
+            None => bail!("json_value_to_rust parse error: {} {:?}", val, meta_type),
+        },
+        (MetaDataType::Double, serde_json::Value::Number(n)) => match n.as_f64() {
+            Some(n) => {
+                let lit = Literal::f64_suffixed(n);
+                quote!(#lit)
+            }
+            None => bail!("json_value_to_rust parse error: {} {:?}", val, meta_type),
+        },
+        (MetaDataType::Bool, serde_json::Value::Bool(b)) => quote!(#b),
+        (MetaDataType::Unknow(t), _) => bail!("Unknow MetaType {}", t),
+        _ => bail!("json_value_to_rust parse error: {} {:?}", val, meta_type),
     };
     Ok(v)
 }
 
+fn int_literal(n: i64) -> TokenStream {
+    let lit = Literal::i64_unsuffixed(n);
+    quote!(#lit)
+}
+
 fn into_test_cases_iter<'a>(
     mut iter: impl Iterator<Item = serde_json::Value> + 'a,
     len: usize,
@@ -328,17 +745,97 @@ fn into_test_cases_iter<'a>(
     })
 }
 
-fn get_class_output(question: &Question) -> Result<(&str, &str)> {
-    let (method_str, params_str) = {
-        let test_cases_str = if let Some(s) = question.example_testcases.as_ref() {
-            s
-        } else {
-            &question.sample_test_case
-        };
-        parse_class_test_cases2(test_cases_str).unwrap()
+/// Pair the inputs of `exampleTestcases` with the output of the example showing the same
+/// parameter values, or of the example at the same position. Examples missing from
+/// `exampleTestcases` are added when every parameter can be read from them.
+fn example_cases(
+    examples: &[Example],
+    param_names: &[&str],
+    inputs: Vec<Vec<serde_json::Value>>,
+) -> Vec<(Vec<serde_json::Value>, Option<serde_json::Value>)> {
+    let named_inputs = examples
+        .iter()
+        .map(|e| {
+            param_names
+                .iter()
+                .map(|name| e.input_value(name))
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Vec<_>>();
+    let positional = examples.len() == inputs.len();
+
+    let mut used = vec![false; examples.len()];
+    let mut cases = vec![];
+    for (i, inputs) in inputs.into_iter().enumerate() {
+        let matched = named_inputs
+            .iter()
+            .position(|named| named.as_ref() == Some(&inputs))
+            .or(if positional { Some(i) } else { None });
+        let output = matched.and_then(|j| {
+            used[j] = true;
+            examples[j].output_value()
+        });
+        cases.push((inputs, output));
+    }
+    for (j, named) in named_inputs.into_iter().enumerate() {
+        if let (false, Some(inputs)) = (used[j], named) {
+            cases.push((inputs, examples[j].output_value()));
+        }
+    }
+    cases
+}
+
+/// Put `#[test_object]` on the `impl` block of the class, which implements `TestObject` for it.
+fn add_test_object_attr(code: &str, classname: &str) -> Option<String> {
+    let head = format!("impl {classname}");
+    let mut found = false;
+    let mut lines = vec![];
+    for line in code.lines() {
+        let rest = line.trim_start().strip_prefix(&head);
+        if !found && rest.is_some_and(|r| r.trim_start().starts_with(['{', '<']) || r.is_empty()) {
+            found = true;
+            lines.push(format!(
+                "{}{TEST_OBJECT_ATTR}",
+                &line[..line.len() - line.trim_start().len()]
+            ));
+        }
+        lines.push(line.to_owned());
+    }
+    found.then(|| lines.join("\n"))
+}
+
+fn class_cases(question: &Question) -> Vec<(&str, &str)> {
+    let test_cases_str = if let Some(s) = question.example_testcases.as_ref() {
+        s
+    } else {
+        &question.sample_test_case
     };
+    parse_class_test_cases2(test_cases_str)
+}
 
-    Ok((method_str, params_str))
+/// The output of the example showing the same calls as the `i`th case, or of the `i`th example
+/// when every case has one.
+fn class_output(
+    examples: &[Example],
+    methods_json: &str,
+    params_json: &str,
+    i: usize,
+    positional: bool,
+) -> Option<serde_json::Value> {
+    let calls = (
+        serde_json::from_str::<serde_json::Value>(methods_json).ok(),
+        serde_json::from_str::<serde_json::Value>(params_json).ok(),
+    );
+    let matched = examples.iter().find(|e| {
+        let mut lines = e.input.lines().map(|l| serde_json::from_str(l.trim()).ok());
+        (lines.next().flatten(), lines.next().flatten()) == calls
+    });
+    let example = match matched {
+        Some(e) => Some(e),
+        None if positional => examples.get(i),
+        None => None,
+    };
+    example.and_then(Example::output_value)
 }
 
 fn into_array(json: &str) -> Option<Vec<serde_json::Value>> {
@@ -352,7 +849,7 @@ fn into_array(json: &str) -> Option<Vec<serde_json::Value>> {
 fn format_params<'a, 'b>(
     vals: impl Iterator<Item = &'a serde_json::Value>,
     param_types: impl Iterator<Item = &'b MetaDataType>,
-) -> String {
+) -> Vec<TokenStream> {
     vals.zip(param_types)
         .filter_map(
             |(val, param_type)| match json_value_to_rust(val, param_type) {
@@ -363,34 +860,107 @@ fn format_params<'a, 'b>(
                 }
             },
         )
-        .collect::<Vec<String>>()
-        .join(",")
+        .collect()
 }
 
-fn json_to_rust(prefix: &str, param_type: &MetaDataType) -> String {
-    match param_type {
-        MetaDataType::Integer => format!("{prefix}.as_i64().unwrap() as i32"),
-        MetaDataType::String => format!("{prefix}.as_str().unwrap().to_owned()"),
-        MetaDataType::Character => format!("{prefix}.as_str().unwrap().chars().next().unwrap()"),
-        MetaDataType::ListNode => {
-            format!("ListNode::from_jsonstr({prefix}.as_str().unwrap())")
-        }
-        MetaDataType::TreeNode => {
-            format!("TreeNode::from_jsonstr({prefix}.as_str().unwrap())")
-        }
-        MetaDataType::List(ref sub_meta_type) => {
-            let sub_type = json_to_rust("pp", sub_meta_type);
-            format!("{prefix}.as_array().unwrap().iter().map(|pp| {sub_type}).collect()")
-        }
-        MetaDataType::Bool => format!("{prefix}.as_bool().unwrap()"),
-        MetaDataType::Void => prefix.into(),
-        MetaDataType::Unknow(_) => prefix.into(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_value_to_rust() {
+        let ty: MetaDataType = "string[]".into();
+        let val = serde_json::json!(["say \"hi\"", "a\\b"]);
+        let code = json_value_to_rust(&val, &ty).unwrap().to_string();
+        assert_eq!(
+            code,
+            r#"vec ! ["say \"hi\"" . to_owned () , "a\\b" . to_owned ()]"#
+        );
+
+        let code = json_value_to_rust(&serde_json::json!([1, -2]), &"integer[]".into()).unwrap();
+        assert_eq!(code.to_string(), "vec ! [1 , - 2]");
+
+        let code = json_value_to_rust(&serde_json::json!(3000000000i64), &"long".into()).unwrap();
+        assert_eq!(code.to_string(), "3000000000i64");
+
+        let code = json_value_to_rust(&serde_json::json!([0.5, 2]), &"double[]".into()).unwrap();
+        assert_eq!(code.to_string(), "vec ! [0.5f64 , 2f64]");
+    }
+
+    #[test]
+    fn test_example_cases() {
+        use serde_json::json;
+        let content = "<p><strong>示例 1：</strong></p><pre><strong>输入：</strong>nums = [3,3], target = 6\n<strong>输出：</strong>[0,1]</pre><p><strong>示例 2：</strong></p><pre><strong>输入：</strong>nums = [2,7,11,15], target = 9\n<strong>输出：</strong>[0,1]\n<strong>解释：</strong>因为 nums[0] + nums[1] == 9</pre><p><strong>示例 3：</strong></p><pre><strong>输入：</strong>nums = [1,2], target = 3\n<strong>输出：</strong>[0,1]</pre>";
+        let cases = example_cases(
+            &parse_examples(content),
+            &["nums", "target"],
+            vec![
+                vec![json!([2, 7, 11, 15]), json!(9)],
+                vec![json!([3, 3]), json!(6)],
+            ],
+        );
+        assert_eq!(
+            cases,
+            vec![
+                (vec![json!([2, 7, 11, 15]), json!(9)], Some(json!([0, 1]))),
+                (vec![json!([3, 3]), json!(6)], Some(json!([0, 1]))),
+                (vec![json!([1, 2]), json!(3)], Some(json!([0, 1]))),
+            ]
+        );
     }
-}
 
-fn rust_to_json_value(res: &str, param_type: &MetaDataType) -> Option<String> {
-    match param_type {
-        MetaDataType::Void => None,
-        _ => Some(format!("json!({res})")),
+    #[test]
+    fn test_add_test_object_attr() {
+        let code = "struct MinStack {\n\n}\n\n/**\n * `&self` means the method has a read-only reference.\n */\nimpl MinStack {\n\n    fn new() -> Self {\n\n    }\n}";
+        assert_eq!(
+            add_test_object_attr(code, "MinStack").unwrap(),
+            "struct MinStack {\n\n}\n\n/**\n * `&self` means the method has a read-only reference.\n */\n#[test_object]\nimpl MinStack {\n\n    fn new() -> Self {\n\n    }\n}"
+        );
+        assert_eq!(add_test_object_attr(code, "Min"), None);
+    }
+
+    #[test]
+    fn test_rust_snippet() {
+        let mut meta: MetaData = serde_json::from_str(
+            r#"{"name":"cloneGraph","params":[{"name":"node","type":"Node"}],"return":{"type":"Node"}}"#,
+        )
+        .unwrap();
+        assert_eq!(rust_snippet(&meta), None);
+        meta.resolve_nodes(crate::meta::NodeKind::Graph);
+        assert_eq!(
+            rust_snippet(&meta).unwrap(),
+            "impl Solution {\n    pub fn clone_graph(node: Option<Rc<RefCell<GraphNode>>>) -> Option<Rc<RefCell<GraphNode>>> {\n\n    }\n}"
+        );
+    }
+
+    #[test]
+    fn test_variants_const() {
+        let meta: MetaData = serde_json::from_str(r#"{"name":"rotate","params":[{"name":"nums","type":"integer[]"},{"name":"k","type":"integer"}],"return":{"type":"void"}}"#).unwrap();
+        let params = match meta {
+            MetaData::Base { params, .. } => params,
+            _ => unreachable!(),
+        };
+        let method = format_ident!("rotate");
+        let code = variants_const(&params, &MetaDataType::Void, Some(0), &method)
+            .unwrap()
+            .to_string();
+        assert!(code.ends_with("const VARIANTS : & [(& str , fn (& mut Vec < i32 > , i32))] = & [(\"Solution\" , Solution :: rotate)] ;"));
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let val = serde_json::json!(["x\"y"]);
+        let arg = json_value_to_rust(&val, &"TreeNode".into()).unwrap();
+        let code = pretty_print(quote! {
+            #[test]
+            pub fn test_a() {
+                assert_eq!(Solution::a(#arg), 1);
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            code,
+            "#[test]\npub fn test_a() {\n    assert_eq!(Solution::a(TreeNode::from_jsonstr(\"[\\\"x\\\\\\\"y\\\"]\")), 1);\n}\n"
+        );
     }
 }
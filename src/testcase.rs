@@ -1,4 +1,10 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::{bail, Ok, Result};
+use async_std::{fs::OpenOptions, io::WriteExt};
+use serde_json::Value;
+
+pub const TESTCASES_DIR: &str = "testcases";
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ClassTestCase {
@@ -36,23 +42,66 @@ pub fn parse_class_test_cases(s: &str) -> Result<impl Iterator<Item = ClassTestC
     }
 }
 
-pub fn parse_class_test_cases2(s: &str) -> Option<(&str, &str)> {
-    let mut sp = s.split('\n');
+/// Split the `exampleTestcases` of a design problem into (methods, params) line pairs, one per example.
+pub fn parse_class_test_cases2(s: &str) -> Vec<(&str, &str)> {
+    let lines = s
+        .split('\n')
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    lines
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
 
-    let n1 = sp.next();
-    let n2 = sp.next();
-    if let (Some(line1), Some(line2)) = (n1, n2) {
-        Some((line1, line2))
-    } else {
-        None
+pub fn testcases_path(project_dir: &Path, title_slug: &str) -> PathBuf {
+    project_dir
+        .join(TESTCASES_DIR)
+        .join(format!("{title_slug}.txt"))
+}
+
+/// Render cases in the testcase file format read by `prelude::load_testcases`:
+/// a comment naming the parameters, then per case one JSON line per input followed by
+/// the expected output, cases separated by a blank line.
+pub fn format_testcases(param_names: &[&str], cases: &[(Vec<Value>, Option<Value>)]) -> String {
+    let mut content = format!("# {} -> expected\n", param_names.join(", "));
+    for (inputs, expected) in cases {
+        for input in inputs.iter().chain(expected.iter()) {
+            content.push('\n');
+            content.push_str(&input.to_string());
+        }
+        content.push('\n');
     }
+    content
+}
+
+/// Append a case, as reported by a failed submission, to the testcase file.
+pub async fn append_testcase(path: &Path, inputs: &str, expected: &str) -> Result<()> {
+    let mut case = String::from("\n");
+    for line in inputs.lines().chain(std::iter::once(expected)) {
+        let value: Value = serde_json::from_str(line.trim())?;
+        case.push_str(&value.to_string());
+        case.push('\n');
+    }
+    if let Some(dir) = path.parent() {
+        async_std::fs::create_dir_all(dir).await?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(case.as_bytes()).await?;
+    file.flush().await?;
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
+    // #[test]
     // fn test_parse() {
     //     let a = parse_class_test_cases("[\"AllOne\",\"inc\",\"inc\",\"getMaxKey\",\"getMinKey\",\"inc\",\"getMaxKey\",\"getMinKey\"]\n[[],[\"hello\"],[\"hello\"],[],[],[\"leet\"],[],[]]").unwrap();
     //     println!("--> testcases: {:?}", a.collect::<Vec<_>>())
@@ -75,4 +124,33 @@ mod tests {
             println!("--> testcases: {:?}", i)
         }
     }
+
+    #[test]
+    fn test_parse_class() {
+        let cases = parse_class_test_cases2(
+            "[\"MyQueue\",\"push\",\"pop\"]\n[[],[1],[]]\n[\"MyQueue\",\"empty\"]\n[[],[]]",
+        );
+        assert_eq!(
+            cases,
+            vec![
+                ("[\"MyQueue\",\"push\",\"pop\"]", "[[],[1],[]]"),
+                ("[\"MyQueue\",\"empty\"]", "[[],[]]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_testcases() {
+        let cases = vec![
+            (
+                vec![serde_json::json!([2, 7, 11, 15]), serde_json::json!(9)],
+                Some(serde_json::json!([0, 1])),
+            ),
+            (vec![serde_json::json!([3, 3]), serde_json::json!(6)], None),
+        ];
+        assert_eq!(
+            format_testcases(&["nums", "target"], &cases),
+            "# nums, target -> expected\n\n[2,7,11,15]\n9\n[0,1]\n\n[3,3]\n6\n"
+        );
+    }
 }
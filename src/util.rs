@@ -28,6 +28,19 @@ pub fn get_title_slug(s: &str) -> Cow<'_, str> {
     }
 }
 
+/// Title slug from the `/// src: https://leetcode.cn/problems/<slug>/` header line.
+pub fn parse_src_slug(line: &str) -> Option<&str> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"/problems/(\S+)/").unwrap();
+    }
+    if !line.starts_with("/// src:") {
+        return None;
+    }
+    RE.captures(line)
+        .and_then(|caps| caps.get(1))
+        .map(|v| v.as_str())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,4 +67,14 @@ mod tests {
             get_title_slug(" https://leetcode.cn/problems/minimum-number-of-refueling-stops/");
         assert_eq!(name, "minimum-number-of-refueling-stops");
     }
+
+    #[test]
+    fn test_parse_src_slug() {
+        let slug = parse_src_slug("/// src: https://leetcode.cn/problems/two-sum/");
+        assert_eq!(slug, Some("two-sum"));
+        assert_eq!(
+            parse_src_slug("// src: https://leetcode.cn/problems/two-sum/"),
+            None
+        );
+    }
 }